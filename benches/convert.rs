@@ -0,0 +1,65 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+// Converting many small tokens of a source: one thread-local lookup per
+// token compared with the batch APIs.
+//
+// cargo bench --bench convert
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use zcstring::{source_handle, ZCString};
+
+fn convert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convert");
+    let source = ZCString::from("cat dog frog owl ".repeat(25_000));
+    let tokens: Vec<&str> = source.split_ascii_whitespace().collect();
+    group.throughput(Throughput::Elements(tokens.len() as u64));
+
+    // thread-local lookup for every token
+    group.bench_function("from_str_with_source", |b| {
+        let _guard = source.get_source_guard();
+        b.iter(|| {
+            black_box(&tokens)
+                .iter()
+                .map(|t| ZCString::from_str_with_source(t))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // thread-local lookup once per batch
+    group.bench_function("source_handle", |b| {
+        let _guard = source.get_source_guard();
+        b.iter(|| {
+            let handle = source_handle();
+            black_box(&tokens)
+                .iter()
+                .map(|t| handle.convert(t))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // no thread-local source at all
+    group.bench_function("substrs_from", |b| {
+        b.iter(|| source.substrs_from(black_box(&tokens).iter().copied()))
+    });
+
+    group.bench_function("from_str_without_source", |b| {
+        b.iter(|| {
+            black_box(&tokens)
+                .iter()
+                .map(|t| ZCString::from_str_without_source(t))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, convert);
+criterion_main!(benches);
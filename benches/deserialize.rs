@@ -0,0 +1,121 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+// JSON deserialization into ZCString compared with String and borrowed
+// &str, plus the fixed per call cost of serde_json_from_zcstring.
+//
+// cargo bench --bench deserialize
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde::Deserialize;
+use zcstring::{serde_json_from_zcstring, serde_json_from_zcstring_seed, ZCString, ZCStringSeed};
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct ZcEntry {
+    host: ZCString,
+    level: ZCString,
+    user: ZCString,
+    path: ZCString,
+    message: ZCString,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct StringEntry {
+    host: String,
+    level: String,
+    user: String,
+    path: String,
+    message: String,
+}
+
+// only works for strings without escapes
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct StrEntry<'a> {
+    host: &'a str,
+    level: &'a str,
+    user: &'a str,
+    path: &'a str,
+    message: &'a str,
+}
+
+// a JSON array of count log entries, without escapes so &str can borrow
+fn document(count: usize) -> ZCString {
+    let entries: Vec<String> = (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"host": "web-{:02}.example.com", "level": "{}", "user": "user{}", "path": "/api/v1/items/{}", "message": "request {} served from cache after a short wait"}}"#,
+                i % 16,
+                ["info", "warning", "error"][i % 3],
+                i % 1000,
+                i,
+                i,
+            )
+        })
+        .collect();
+
+    ZCString::from(format!("[{}]", entries.join(",")))
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize");
+
+    for count in [1, 100, 10_000] {
+        let doc = document(count);
+        group.throughput(Throughput::Bytes(doc.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("ZCString", count), &doc, |b, doc| {
+            b.iter(|| serde_json_from_zcstring::<Vec<ZcEntry>>(black_box(doc.clone())).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("String", count), &doc, |b, doc| {
+            b.iter(|| serde_json::from_str::<Vec<StringEntry>>(black_box(doc)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("&str", count), &doc, |b, doc| {
+            b.iter(|| serde_json::from_str::<Vec<StrEntry>>(black_box(doc)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+// costs paid once per serde_json_from_zcstring call regardless of document
+// size: cloning the source and installing it as the thread-local source
+fn per_call_overhead(c: &mut Criterion) {
+    let mut group = c.benchmark_group("per_call_overhead");
+    let json = ZCString::from(r#""cats""#);
+
+    group.bench_function("clone", |b| b.iter(|| black_box(&json).clone()));
+
+    group.bench_function("source_guard", |b| {
+        b.iter(|| drop(black_box(&json).get_source_guard()))
+    });
+
+    // thread-local source, takes the source by value
+    group.bench_function("serde_json_from_zcstring", |b| {
+        b.iter(|| serde_json_from_zcstring::<ZCString>(black_box(&json).clone()).unwrap())
+    });
+
+    // explicit source, no clone of the source nor thread-local access
+    group.bench_function("serde_json_from_zcstring_seed", |b| {
+        b.iter(|| serde_json_from_zcstring_seed(black_box(&json), ZCStringSeed(&json)).unwrap())
+    });
+
+    group.bench_function("String", |b| {
+        b.iter(|| serde_json::from_str::<String>(black_box(&json)).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, deserialize, per_call_overhead);
+criterion_main!(benches);
@@ -80,7 +80,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 // now serialize - Ok we could do a zero-alloc deserialize but
                 //                 not right now...
                 println!("  Serialized: {}", serde_json::to_string(&entry)?);
-                println!("");
+                println!();
 
                 Ok(entry)
             })
@@ -16,8 +16,13 @@ fn main() {
     #[cfg(feature = "std")]
     println!("From String: {:?}", ZCString::from(String::from("str")));
     #[cfg(feature = "std")]
-    println!("String::from(\"a\") == ZCString::from(\"a\"): {:?}", 
-        String::from("a") == ZCString::from("a"));
+    {
+        let (s, zc) = (String::from("a"), ZCString::from("a"));
+        println!(
+            "String::from(\"a\") == ZCString::from(\"a\"): {:?}",
+            s == zc
+        );
+    }
     println!("New ZCString: {:?}", ZCString::new());
 
     // how big is a ZCString member in a structure as compared &str?
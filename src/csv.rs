@@ -0,0 +1,484 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `csv` integration yielding zero-copy fields and records.
+
+use crate::ZCString;
+use ::csv::{Reader, ReaderBuilder, StringRecord};
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use std::io::Read;
+use std::marker::PhantomData;
+
+/// Error reading or deserializing a CSV record.
+#[derive(thiserror::Error, Debug)]
+pub enum ZCCsvError {
+    #[error("CSV failure: {0}")]
+    Csv(#[from] ::csv::Error),
+
+    #[error("Record {record}: {source}")]
+    Deserialize {
+        /// Zero based index of the record, not counting the header row.
+        record: u64,
+        source: Error,
+    },
+}
+
+/// Reads CSV records from a [`ZCString`] as lists of zero-copy fields.
+///
+/// Unquoted fields and quoted fields without escaped quotes are sub-slices
+/// of the source, fields containing doubled quotes (`""`) are copied. Uses
+/// the `csv` defaults: comma delimited with `"` quotes.
+///
+/// **Requires the `csv` feature.**
+///
+/// ### Example
+/// ```
+/// # use zcstring::{ZCCsvReader, ZCString};
+/// let csv = ZCString::from("name,motto\ncat,\"meow, meow\"\ndog,\"say \"\"woof\"\"\"\n");
+/// let mut reader = ZCCsvReader::new(csv.clone(), true);
+///
+/// assert_eq!(reader.headers().unwrap(), ["name", "motto"]);
+///
+/// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records[0], ["cat", "meow, meow"]);
+/// assert_eq!(records[1], ["dog", "say \"woof\""]);
+///
+/// assert!(csv.source_of(&records[0][0]));
+/// assert!(csv.source_of(&records[0][1]));
+/// assert!(csv.source_of(&records[1][0]));
+/// assert!(!csv.source_of(&records[1][1]));
+/// ```
+///
+/// Fields that can't be located in the source, such as text following a
+/// closing quote, are copied, as are the fields after them.
+/// ```
+/// # use zcstring::{ZCCsvReader, ZCString};
+/// let csv = ZCString::from("\"ab\"é,d\n");
+/// let record = ZCCsvReader::new(csv.clone(), false).next().unwrap().unwrap();
+///
+/// assert_eq!(record, ["abé", "d"]);
+/// assert!(!csv.source_of(&record[0]));
+/// ```
+///
+/// Reading a file mixing quoted and unquoted fields:
+/// ```
+/// # use zcstring::{ZCCsvReader, ZCString};
+/// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/mixed.csv");
+/// let csv = ZCString::from_file(path).unwrap();
+/// let mut reader = ZCCsvReader::new(csv.clone(), true);
+///
+/// assert_eq!(reader.headers().unwrap(), ["name", "motto", "legs"]);
+///
+/// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records[0], ["cat", "meow, meow", "4"]);
+/// assert_eq!(records[1], ["bird", "tweet", "2"]);
+/// assert_eq!(records[2], ["dog", "say \"woof\"", "4"]);
+/// assert_eq!(records[3], ["fish", "", "0"]);
+///
+/// // all but the escaped motto borrow from the file's contents
+/// for (i, field) in records.iter().flatten().enumerate() {
+///     assert_eq!(csv.source_of(field), i != 7);
+/// }
+/// ```
+pub struct ZCCsvReader {
+    source: ZCString,
+    reader: Reader<SourceReader>,
+    record: StringRecord,
+}
+
+impl ZCCsvReader {
+    /// Creates a reader over `source`, treating the first row as headers
+    /// if `has_headers` is set.
+    pub fn new(source: ZCString, has_headers: bool) -> Self {
+        let reader = ReaderBuilder::new()
+            .has_headers(has_headers)
+            .from_reader(SourceReader {
+                source: source.clone(),
+                offset: 0,
+            });
+
+        ZCCsvReader {
+            source,
+            reader,
+            record: StringRecord::new(),
+        }
+    }
+
+    /// Returns the header row, empty if the reader was created without
+    /// headers.
+    pub fn headers(&mut self) -> Result<Vec<ZCString>, ::csv::Error> {
+        if !self.reader.has_headers() {
+            return Ok(Vec::new());
+        }
+
+        let headers = self.reader.headers()?;
+        let fields = map_fields(&self.source, headers);
+        Ok(fields.iter().map(|f| self.source.from_substr(f)).collect())
+    }
+
+    // reads the next record into self.record, returns false at the end
+    fn read_record(&mut self) -> Result<bool, ::csv::Error> {
+        self.reader.read_record(&mut self.record)
+    }
+}
+
+impl Iterator for ZCCsvReader {
+    type Item = Result<Vec<ZCString>, ::csv::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_record() {
+            Ok(true) => {
+                let fields = map_fields(&self.source, &self.record);
+                Some(Ok(fields
+                    .iter()
+                    .map(|f| self.source.from_substr(f))
+                    .collect()))
+            }
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Deserializes each record of the CSV document `source` into `T`, with
+/// `source` active so `ZCString` fields borrow from it as described for
+/// [`ZCCsvReader`].
+///
+/// With `has_headers` records are deserialized as maps keyed by the header
+/// row, so struct fields are matched by name, otherwise as sequences.
+/// Scalars such as numbers and booleans are parsed from their text, empty
+/// fields deserialize as `None` for `Option`s.
+///
+/// **Requires the `csv` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{csv_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Pet {
+///     name: ZCString,
+///     legs: u32,
+///     motto: Option<ZCString>,
+/// }
+///
+/// let csv = ZCString::from("legs,name,motto\n4,cat,\"meow, \"\"meow\"\"\"\n2,bird,\n");
+/// let pets = csv_from_zcstring::<Pet>(csv.clone(), true)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(pets[0].legs, 4);
+/// assert!(csv.source_of(&pets[0].name));
+/// assert_eq!(pets[0].motto.as_ref().unwrap(), "meow, \"meow\"");
+/// assert!(!csv.source_of(pets[0].motto.as_ref().unwrap()));
+///
+/// assert!(csv.source_of(&pets[1].name));
+/// assert_eq!(pets[1].motto, None);
+///
+/// let rows = csv_from_zcstring::<(ZCString, u8)>("cat,4\n".into(), false)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(rows[0].1, 4);
+/// ```
+pub fn csv_from_zcstring<T>(source: ZCString, has_headers: bool) -> ZCCsvRecords<T>
+where
+    T: DeserializeOwned,
+{
+    ZCCsvRecords {
+        reader: ZCCsvReader::new(source, has_headers),
+        headers: None,
+        index: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// Iterator over the records of a CSV document deserialized into `T`.
+///
+/// Created via [`csv_from_zcstring`].
+pub struct ZCCsvRecords<T> {
+    reader: ZCCsvReader,
+    headers: Option<StringRecord>,
+    index: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> ZCCsvRecords<T> {
+    fn deserialize(&mut self) -> Result<Option<T>, ZCCsvError> {
+        if self.headers.is_none() && self.reader.reader.has_headers() {
+            self.headers = Some(self.reader.reader.headers()?.clone());
+        }
+
+        if !self.reader.read_record()? {
+            return Ok(None);
+        }
+
+        let source = &self.reader.source;
+        let record = RecordDeserializer {
+            headers: self.headers.as_ref().map(|h| map_fields(source, h)),
+            fields: map_fields(source, &self.reader.record),
+        };
+
+        let index = self.index;
+        self.index += 1;
+
+        ZCString::with_source(source.clone(), |_| T::deserialize(record))
+            .map(Some)
+            .map_err(|source| ZCCsvError::Deserialize {
+                record: index,
+                source,
+            })
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for ZCCsvRecords<T> {
+    type Item = Result<T, ZCCsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deserialize().transpose()
+    }
+}
+
+// Read over the bytes of a ZCString
+struct SourceReader {
+    source: ZCString,
+    offset: usize,
+}
+
+impl Read for SourceReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = &self.source.as_bytes()[self.offset..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+// Locates the fields of record within source, returning slices of source
+// where the field text appears verbatim and the record's own (unescaped)
+// field otherwise.
+fn map_fields<'a>(source: &'a str, record: &'a StringRecord) -> Vec<&'a str> {
+    let mut fields: Vec<&'a str> = record.iter().collect();
+
+    let Some(start) = record.position().map(|p| p.byte() as usize) else {
+        return fields;
+    };
+
+    let Some(raw) = source.get(start..) else {
+        return fields;
+    };
+
+    let mut cursor = 0;
+    for field in fields.iter_mut() {
+        let Some(rest) = raw.get(cursor..) else {
+            return fields;
+        };
+
+        let (found, length) = match rest.strip_prefix('"') {
+            Some(quoted) => match closing_quote(quoted) {
+                // text after the closing quote ("ab"c) belongs to the field
+                // too, its length is unknown so leave the rest as is
+                Some(end) if !ends_field(quoted.as_bytes().get(end + 1)) => return fields,
+                // no doubled quotes, the text between the quotes is the field
+                Some(end) if end == field.len() && quoted.starts_with(*field) => {
+                    (Some(&quoted[..end]), end + 2)
+                }
+                Some(end) => (None, end + 2),
+                None => return fields,
+            },
+            None if rest.starts_with(*field) => (Some(&rest[..field.len()]), field.len()),
+            // not where we expected it, leave the rest as is
+            None => return fields,
+        };
+
+        if let Some(found) = found {
+            *field = found;
+        }
+
+        // skip the delimiter
+        cursor += length + 1;
+        if cursor > raw.len() {
+            break;
+        }
+    }
+
+    fields
+}
+
+// whether byte, following a field, ends it
+fn ends_field(byte: Option<&u8>) -> bool {
+    matches!(byte, None | Some(b',' | b'\r' | b'\n'))
+}
+
+// byte offset of the quote closing a quoted field, skipping doubled quotes
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let bytes = quoted.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'"' {
+            if bytes.get(i + 1) == Some(&b'"') {
+                i += 2;
+                continue;
+            }
+            return Some(i);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+// deserializes a record as a map keyed by headers, or as a sequence
+struct RecordDeserializer<'a> {
+    headers: Option<Vec<&'a str>>,
+    fields: Vec<&'a str>,
+}
+
+impl<'de> Deserializer<'de> for RecordDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.headers {
+            Some(_) => self.deserialize_map(visitor),
+            None => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let headers = self.headers.unwrap_or_default();
+        let entries = headers
+            .into_iter()
+            .zip(self.fields)
+            .map(|(h, f)| (FieldDeserializer(h), FieldDeserializer(f)));
+
+        visitor.visit_map(MapDeserializer::new(entries))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let fields = self.fields.into_iter().map(FieldDeserializer);
+        visitor.visit_seq(SeqDeserializer::new(fields))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct enum
+        identifier ignored_any
+    }
+}
+
+// deserializes a single field, parsing scalars from its text
+struct FieldDeserializer<'a>(&'a str);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.trim().parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(e) => Err(serde::de::Error::custom(format_args!(
+                        "{}: {:?}",
+                        e, self.0
+                    ))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let de: serde::de::value::BorrowedStrDeserializer<'de, Error> =
+            serde::de::value::BorrowedStrDeserializer::new(self.0);
+        de.deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for FieldDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
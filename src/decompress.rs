@@ -0,0 +1,251 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! Transparent decompression of gzip and zstd inputs.
+
+use crate::{ReaderError, ZCString};
+use arcstr::ArcStr;
+use std::io::{Read, Seek, SeekFrom};
+
+// best case deflate ratio, a recorded uncompressed size beyond this many
+// times the compressed size can't be right and is not trusted
+const MAX_RATIO: u64 = 1032;
+
+/// Compression format of a file or stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+    /// Uncompressed text.
+    Plain,
+    /// gzip, requires the `flate2` feature.
+    Gzip,
+    /// zstd, requires the `zstd` feature.
+    Zstd,
+}
+
+impl Codec {
+    /// Detects the compression format from the leading magic bytes of
+    /// `header`. Anything not recognized is treated as [`Codec::Plain`].
+    pub fn detect(header: &[u8]) -> Codec {
+        if header.starts_with(&[0x1f, 0x8b]) {
+            Codec::Gzip
+        } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Codec::Zstd
+        } else {
+            Codec::Plain
+        }
+    }
+}
+
+impl ZCString {
+    /// Create a ZCString by reading an entire file, decompressing gzip or
+    /// zstd content. The compression is detected by magic bytes, not by
+    /// file extension, uncompressed files are read as by [`Self::from_file`].
+    ///
+    /// When the uncompressed size is recorded in the file (gzip ISIZE or the
+    /// zstd frame header) the data is decompressed directly into the final
+    /// `ArcStr`, otherwise it is decompressed into a growable buffer and
+    /// copied once. The recorded size is not trusted beyond what the
+    /// compressed size allows, a forged size can't force a huge allocation.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    ///
+    /// // uncompressed files pass straight through
+    /// let r = ZCString::from_file_auto(path.join("from_file_test.txt"))?;
+    /// assert_eq!(&r, "xyzzy");
+    ///
+    /// assert_eq!(ZCString::from_file_auto(path.join("empty.txt"))?, "");
+    ///
+    /// // reports a length of 0
+    /// if cfg!(target_os = "linux") {
+    ///     let status = ZCString::from_file_auto("/proc/self/status")?;
+    ///     assert!(status.starts_with("Name:"));
+    /// }
+    ///
+    /// if cfg!(feature = "flate2") {
+    ///     let r = ZCString::from_file_auto(path.join("from_file_test.txt.gz"))?;
+    ///     assert_eq!(&r, "xyzzy");
+    ///
+    ///     // records an uncompressed size of 4 GiB
+    ///     assert!(ZCString::from_file_auto(path.join("from_file_test_bad_size.txt.gz")).is_err());
+    /// }
+    ///
+    /// if cfg!(feature = "zstd") {
+    ///     let r = ZCString::from_file_auto(path.join("from_file_test.txt.zst"))?;
+    ///     assert_eq!(&r, "xyzzy");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_file_auto<P: AsRef<std::path::Path>>(path: P) -> Result<ZCString, ReaderError> {
+        let mut handle = std::fs::File::open(path)?;
+        let metadata = handle.metadata()?;
+
+        if !metadata.is_file() || metadata.len() == 0 {
+            // no usable length (pipes, /proc files), nor reliable seeking
+            let mut buffer = Vec::new();
+            handle.read_to_end(&mut buffer)?;
+            return Self::read_decompressed(buffer.as_slice(), Codec::detect(&buffer));
+        }
+
+        let mut header = [0u8; 4];
+        let header_len = read_prefix(&mut handle, &mut header)?;
+        let codec = Codec::detect(&header[..header_len]);
+
+        if codec == Codec::Plain {
+            return Self::read_range(&mut handle, 0..);
+        }
+
+        let max_size = metadata.len().saturating_mul(MAX_RATIO);
+
+        if let Some(size) = uncompressed_size(&mut handle, codec)?.filter(|&size| size <= max_size)
+        {
+            handle.seek(SeekFrom::Start(0))?;
+
+            if let Some(result) = read_sized(decoder(&mut handle, codec)?, size)? {
+                return Ok(result);
+            }
+
+            // the recorded size was wrong (concatenated members or frames),
+            // start over without it
+        }
+
+        handle.seek(SeekFrom::Start(0))?;
+        Self::read_decompressed(&mut handle, codec)
+    }
+
+    /// Create a ZCString by decompressing everything readable from `input`
+    /// using `codec`. The decompressed bytes must be valid UTF-8.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zcstring::{Codec, ZCString};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # if cfg!(feature = "zstd") {
+    /// let compressed = std::fs::read(concat!(
+    ///     env!("CARGO_MANIFEST_DIR"),
+    ///     "/examples/from_file_test.txt.zst"
+    /// ))?;
+    /// let codec = Codec::detect(&compressed);
+    /// assert_eq!(codec, Codec::Zstd);
+    ///
+    /// let r = ZCString::read_decompressed(Cursor::new(compressed), codec)?;
+    /// assert_eq!(r, "xyzzy");
+    /// # }
+    ///
+    /// assert_eq!(ZCString::read_decompressed(&b""[..], Codec::Plain)?, "");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_decompressed<R: Read>(input: R, codec: Codec) -> Result<ZCString, ReaderError> {
+        let mut buffer = Vec::new();
+        decoder(input, codec)?.read_to_end(&mut buffer)?;
+
+        if buffer.is_empty() {
+            // ArcStr::init_with can't create an empty string
+            return Ok(ZCString::new());
+        }
+
+        let result = ArcStr::init_with(buffer.len(), |dest| dest.copy_from_slice(&buffer))?;
+        Ok(ZCString::from(result))
+    }
+}
+
+// wrap input in a decompressor for codec
+fn decoder<'a, R: Read + 'a>(input: R, codec: Codec) -> Result<Box<dyn Read + 'a>, ReaderError> {
+    match codec {
+        Codec::Plain => Ok(Box::new(input)),
+        #[cfg(feature = "flate2")]
+        Codec::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(input))),
+        #[cfg(feature = "zstd")]
+        Codec::Zstd => Ok(Box::new(zstd::stream::read::Decoder::new(input)?)),
+        #[allow(unreachable_patterns)]
+        _ => Err(ReaderError::UnsupportedCodec(codec)),
+    }
+}
+
+// read up to buffer.len() bytes from the start of the input, returns the
+// number of bytes read
+fn read_prefix<I: Read + Seek>(input: &mut I, buffer: &mut [u8]) -> std::io::Result<usize> {
+    input.seek(SeekFrom::Start(0))?;
+
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match input.read(&mut buffer[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+
+    Ok(filled)
+}
+
+// uncompressed size as recorded by the compressed format, if any
+fn uncompressed_size<I: Read + Seek>(input: &mut I, codec: Codec) -> std::io::Result<Option<u64>> {
+    match codec {
+        Codec::Gzip => {
+            // ISIZE: the last 4 bytes hold the uncompressed size modulo 2^32
+            // smallest possible gzip member is 18 bytes
+            if input.seek(SeekFrom::End(0))? < 18 {
+                return Ok(None);
+            }
+
+            let mut isize = [0u8; 4];
+            input.seek(SeekFrom::End(-4))?;
+            input.read_exact(&mut isize)?;
+            Ok(Some(u32::from_le_bytes(isize) as u64))
+        }
+        #[cfg(feature = "zstd")]
+        Codec::Zstd => {
+            // maximum zstd frame header size
+            let mut header = [0u8; 18];
+            let header_len = read_prefix(input, &mut header)?;
+            Ok(
+                zstd::zstd_safe::get_frame_content_size(&header[..header_len])
+                    .ok()
+                    .flatten(),
+            )
+        }
+        _ => Ok(None),
+    }
+}
+
+// read exactly size bytes into a new ArcStr, returns None if the input does
+// not contain exactly size bytes or size can't be allocated up front
+fn read_sized<R: Read>(mut input: R, size: u64) -> Result<Option<ZCString>, ReaderError> {
+    let size = match usize::try_from(size) {
+        Ok(size) if size > 0 => size,
+        _ => return Ok(None),
+    };
+
+    let mut io_result = Ok(false);
+
+    let result = ArcStr::init_with(size, |buffer| {
+        io_result = (|| -> Result<bool, std::io::Error> {
+            match input.read_exact(buffer) {
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(false),
+                other => other?,
+            }
+
+            // input must be fully consumed
+            Ok(input.read(&mut [0u8; 1])? == 0)
+        })()
+    });
+
+    match io_result? {
+        true => Ok(Some(ZCString::from(result?))),
+        false => Ok(None),
+    }
+}
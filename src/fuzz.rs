@@ -0,0 +1,248 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `arbitrary` and `quickcheck` support for fuzzing code that consumes
+//! `ZCString`s.
+//!
+//! `ZCString` implements `arbitrary::Arbitrary` (feature `arbitrary`) and
+//! `quickcheck::Arbitrary` (feature `quickcheck`). Generated strings are
+//! valid UTF-8 and are sometimes a view into the middle of a larger
+//! generated buffer rather than a whole buffer, so code depending on a
+//! string's offset or parent is exercised too.
+//!
+//! [`SourceAndSubstrs`] generates a source together with strings created
+//! from it through the thread-local source, for fuzzing the zero-copy
+//! invariants themselves.
+//!
+//! **Requires the `arbitrary` or `quickcheck` feature.**
+
+use crate::ZCString;
+use alloc::vec::Vec;
+
+// most strings are whole buffers, one in VIEW_ONE_IN a view of one
+const VIEW_ONE_IN: u8 = 4;
+
+// upper bound of the fields generated for SourceAndSubstrs
+const MAX_SUBSTRS: usize = 8;
+
+/// An arbitrary source string and strings created from slices of it with
+/// [`ZCString::from_str_with_source`] while the source was installed as the
+/// thread-local source.
+///
+/// Every substr is therefore a zero-copy view of the source, except empty
+/// ones at the very end of the source, which do not lie within it.
+///
+/// ### Example
+/// ```
+/// # use arbitrary::{Arbitrary, Unstructured};
+/// # use zcstring::fuzz::SourceAndSubstrs;
+/// let data = b"\x03\x00\x04\x05\x08cats and dogs\x0d";
+/// let generated = SourceAndSubstrs::arbitrary(&mut Unstructured::new(data)).unwrap();
+///
+/// for substr in &generated.substrs {
+///     assert!(substr.is_empty() || substr.is_view_of(&generated.source));
+///     assert_eq!(generated.source.from_substr(substr), *substr);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SourceAndSubstrs {
+    /// The generated source.
+    pub source: ZCString,
+    /// Strings created from slices of `source`.
+    pub substrs: Vec<ZCString>,
+}
+
+impl SourceAndSubstrs {
+    // create a substr of source for each pair of char boundary numbers
+    fn new(source: ZCString, bounds: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let substrs = ZCString::with_source_ref(&source, |source| {
+            bounds
+                .into_iter()
+                .map(|(a, b)| {
+                    let view = view_between(source, a, b);
+                    ZCString::from_str_with_source(view.as_str())
+                })
+                .collect()
+        });
+
+        SourceAndSubstrs { source, substrs }
+    }
+}
+
+// number of char boundaries of s, including both ends
+fn boundaries(s: &str) -> usize {
+    s.chars().count() + 1
+}
+
+// the view of source between the a'th and b'th char boundaries
+fn view_between(source: &ZCString, a: usize, b: usize) -> ZCString {
+    let boundary = |n: usize| {
+        source
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(n)
+            .unwrap_or(source.len())
+    };
+
+    source.substr(boundary(a.min(b))..boundary(a.max(b)))
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use super::{boundaries, view_between, SourceAndSubstrs, MAX_SUBSTRS, VIEW_ONE_IN};
+    use crate::ZCString;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    /// Generates an arbitrary string allocated with
+    /// [`ZCString::from_str_without_source`], or now and then a view into
+    /// such a string, so structs holding `ZCString`s can derive `Arbitrary`
+    /// for fuzzing.
+    ///
+    /// **Requires the `arbitrary` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use arbitrary::{Arbitrary, Unstructured};
+    /// # use zcstring::ZCString;
+    /// #[derive(Debug, Arbitrary)]
+    /// struct LogLine {
+    ///     host: ZCString,
+    ///     message: ZCString,
+    /// }
+    ///
+    /// let mut u = Unstructured::new(b"\x04cats and dogs");
+    /// let line = LogLine::arbitrary(&mut u).unwrap();
+    /// assert!(!line.host.same_buffer(&line.message));
+    ///
+    /// let rest = ZCString::arbitrary_take_rest(Unstructured::new(b"cats")).unwrap();
+    /// assert_eq!(rest, "cats");
+    /// ```
+    impl<'a> Arbitrary<'a> for ZCString {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let source = ZCString::from_str_without_source(<&str>::arbitrary(u)?);
+
+            // out of data ratio() is true, keeping the whole string
+            if u.ratio(VIEW_ONE_IN - 1, VIEW_ONE_IN)? {
+                return Ok(source);
+            }
+
+            let n = boundaries(&source);
+            let (a, b) = (u.choose_index(n)?, u.choose_index(n)?);
+            Ok(view_between(&source, a, b))
+        }
+
+        fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+            <&str>::arbitrary_take_rest(u).map(ZCString::from_str_without_source)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            <&str as Arbitrary>::size_hint(depth)
+        }
+    }
+
+    /// **Requires the `arbitrary` feature.**
+    impl<'a> Arbitrary<'a> for SourceAndSubstrs {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let source = ZCString::from_str_without_source(<&str>::arbitrary(u)?);
+            let n = boundaries(&source);
+
+            let count = u.int_in_range(0..=MAX_SUBSTRS)?;
+            let bounds = (0..count)
+                .map(|_| Ok((u.choose_index(n)?, u.choose_index(n)?)))
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(SourceAndSubstrs::new(source, bounds))
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls {
+    use super::{boundaries, view_between, SourceAndSubstrs, MAX_SUBSTRS, VIEW_ONE_IN};
+    use crate::ZCString;
+    use alloc::boxed::Box;
+    use alloc::string::{String, ToString};
+    use quickcheck::{Arbitrary, Gen};
+
+    // an index below n, n must not be 0
+    fn index(g: &mut Gen, n: usize) -> usize {
+        usize::arbitrary(g) % n
+    }
+
+    /// Generates strings the same way as `String`, now and then returning a
+    /// view into a generated string instead of the whole string. Shrinks
+    /// like `String`, to whole strings.
+    ///
+    /// **Requires the `quickcheck` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use quickcheck::{Arbitrary, Gen};
+    /// # use zcstring::ZCString;
+    /// let mut g = Gen::new(32);
+    ///
+    /// for _ in 0..100 {
+    ///     let s = ZCString::arbitrary(&mut g);
+    ///     assert!(s.parent().len() >= s.len());
+    /// }
+    /// ```
+    impl Arbitrary for ZCString {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let source = ZCString::from_str_without_source(&String::arbitrary(g));
+
+            if index(g, VIEW_ONE_IN as usize) != 0 {
+                return source;
+            }
+
+            let n = boundaries(&source);
+            let (a, b) = (index(g, n), index(g, n));
+            view_between(&source, a, b)
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(
+                self.to_string()
+                    .shrink()
+                    .map(|s| ZCString::from_str_without_source(&s)),
+            )
+        }
+    }
+
+    /// **Requires the `quickcheck` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use quickcheck::{QuickCheck, TestResult};
+    /// # use zcstring::fuzz::SourceAndSubstrs;
+    /// fn views(generated: SourceAndSubstrs) -> TestResult {
+    ///     let source = &generated.source;
+    ///
+    ///     for substr in generated.substrs.iter().filter(|s| !s.is_empty()) {
+    ///         if !substr.is_view_of(source) || source.from_substr(substr) != *substr {
+    ///             return TestResult::failed();
+    ///         }
+    ///     }
+    ///     TestResult::passed()
+    /// }
+    ///
+    /// QuickCheck::new().quickcheck(views as fn(SourceAndSubstrs) -> TestResult);
+    /// ```
+    impl Arbitrary for SourceAndSubstrs {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let source = ZCString::from_str_without_source(&String::arbitrary(g));
+            let n = boundaries(&source);
+
+            let count = index(g, MAX_SUBSTRS + 1);
+            let bounds: alloc::vec::Vec<_> =
+                (0..count).map(|_| (index(g, n), index(g, n))).collect();
+
+            SourceAndSubstrs::new(source, bounds)
+        }
+    }
+}
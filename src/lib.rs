@@ -20,27 +20,66 @@
 //! - **RAII Guards**: Provides a [`SourceGuard`] to safely manage the lifecycle of the
 //!   thread-local source.
 //! - **Serde Integration**: Optional (defaults to on) support for efficient
-//!   zero-copy deserialization via the `serde` feature flag.
+//!   zero-copy deserialization via the `serde` and `serde_json` feature flags.
 //!
 //! ## Crate Features
 //!
-//! * **`default`** By default, serde and std are enabled.
-//! * **`serde`** (Optional): Enables serialization and deserialization support via `serde` and `serde_json`.
-//! * **`std`** (Optional): Enables `From<String>` implementations.
+//! * **`default`** By default, serde_json and std are enabled.
+//! * **`proptest`** (Optional): Implements `proptest` `Arbitrary` for `ZCString` and
+//!   adds strategies in [`proptest`](crate::proptest).
+//! * **`quickcheck`** (Optional): Implements `quickcheck::Arbitrary` for `ZCString` and
+//!   [`fuzz::SourceAndSubstrs`].
+//! * **`rkyv`** (Optional): Implements `rkyv` archiving for `ZCString`, requires Rust
+//!   1.81 or later.
+//! * **`rmp`** (Optional): Implies `serde` and adds [`rmp_from_slice_with_source`] for
+//!   MessagePack.
+//! * **`rusqlite`** (Optional): Implements `rusqlite` `ToSql` and `FromSql` for `ZCString`.
+//! * **`schemars`** (Optional): Implements `schemars::JsonSchema` for `ZCString`,
+//!   requires Rust 1.74 or later.
+//! * **`serde`** (Optional): Enables `Serialize` and `Deserialize` for `ZCString`
+//!   without depending on `serde_json`.
+//! * **`serde_json`** (Optional): Implies `serde` and adds `serde_json` helpers such as
+//!   [`serde_json_from_zcstring`].
+//! * **`serde_yaml`** (Optional): Implies `serde` and adds [`serde_yaml_from_zcstring`].
+//! * **`sqlx-postgres`**, **`sqlx-sqlite`** (Optional): Implement `sqlx` `Type`, `Encode`
+//!   and `Decode` for `ZCString` with the given backend.
+//! * **`std`** (Optional): Enables the thread-local source, readers and files and
+//!   `From<String>` implementations. Without it the crate is `no_std` and only needs
+//!   `alloc`, strings are then always allocated rather than borrowed from a source.
+//! * **`toml`** (Optional): Implies `serde` and `std` and adds [`toml_from_zcstring`],
+//!   requires Rust 1.76 or later.
+//! * **`tokio`** (Optional): Enables async file and reader constructors.
+//! * **`flate2`** (Optional): Enables gzip decompression in `from_file_auto`.
+//! * **`zstd`** (Optional): Enables zstd decompression in `from_file_auto`.
+//! * **`arbitrary`** (Optional): Implements `arbitrary::Arbitrary` for `ZCString` and
+//!   [`fuzz::SourceAndSubstrs`].
+//! * **`bytes`** (Optional): Adds conversions from `bytes::Bytes` such as
+//!   [`ZCString::try_from_bytes`].
+//! * **`csv`** (Optional): Enables zero-copy CSV records via [`ZCCsvReader`] and
+//!   [`csv_from_zcstring`].
+//! * **`graphemes`** (Optional): Enables zero-copy grapheme cluster iteration via
+//!   `graphemes_zc`.
+//! * **`regex`** (Optional): Enables zero-copy regex capture groups via `captures_zc`.
 //! ## serde_json example
 //!
 //! ```rust
 //! use arcstr::literal;
+//! # #[cfg(feature = "serde_json")]
 //! use serde::Deserialize;
 //! use std::error::Error;
+//! # #[cfg(feature = "serde_json")]
 //! use zcstring::{ZCString, serde_json_from_zcstring};
 //!
+//! # #[cfg(feature = "serde_json")]
 //! #[derive(Debug, Deserialize)]
 //! struct Animal {
 //!    animal: ZCString,
 //!    color: ZCString,
 //! }
 //!
+//! # #[cfg(not(feature = "serde_json"))]
+//! # fn main() {}
+//! # #[cfg(feature = "serde_json")]
 //! fn main() -> Result<(), Box<dyn Error>> {
 //!     let json = literal!(r#"{"animal":"cat", "color": "red"}"#);
 //!     // ZCString::from below is zero-copy from the ArcStr json
@@ -53,21 +92,323 @@
 //!```
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use arcstr::{literal, ArcStr, Substr};
+extern crate alloc;
+
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "csv")]
+pub use csv::{csv_from_zcstring, ZCCsvError, ZCCsvReader, ZCCsvRecords};
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+mod decompress;
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+pub use decompress::Codec;
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+pub mod fuzz;
 #[cfg(feature = "serde_json")]
+mod pointer;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde_json")]
+pub use pointer::{zc_json_get_str, zc_json_pointer};
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+mod sqlx;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "regex")]
+pub use regex::{captures_zc, ZCCaptures};
+
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use arcstr::{literal, ArcStr, Substr};
+use core::ops::{Bound, Deref, RangeBounds};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize};
+#[cfg(feature = "std")]
 use std::cell::RefCell;
 #[cfg(feature = "std")]
-use std::io::{Read, Seek, SeekFrom};
-use std::ops::Deref;
+use std::io::{BufRead, Read, Seek, SeekFrom};
 #[cfg(feature = "std")]
-use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
 
+#[cfg(feature = "std")]
 thread_local! {
-    /// The thread-local storage holding the current active source string.
-    static SOURCE: RefCell<Option<ZCString>> =
+    /// The thread-local stack of active source strings, innermost last.
+    static SOURCE: RefCell<Vec<ZCString>> =
+        const { RefCell::new(Vec::new()) };
+
+    /// Hook called when a string could not be created zero-copy.
+    static FALLBACK_HOOK: RefCell<Option<SharedFallbackHook>> =
         const { RefCell::new(None) };
+
+    /// What to do when a string could not be created zero-copy.
+    static FALLBACK_POLICY: RefCell<FallbackPolicy> =
+        const { RefCell::new(FallbackPolicy::Allocate) };
+}
+
+/// Callback invoked with the string whenever zero-copy creation misses,
+/// see [`set_fallback_hook`].
+#[cfg(feature = "std")]
+pub type FallbackHook = Box<dyn Fn(&str)>;
+
+// shared form of FallbackHook, cloned out of the thread-local before calling
+#[cfg(feature = "std")]
+type SharedFallbackHook = Rc<dyn Fn(&str)>;
+
+/// Sets a hook for the current thread that is called with the string
+/// whenever a `ZCString` falls back to allocating instead of borrowing from
+/// the thread-local source. Pass `None` to remove the hook.
+///
+/// The hook fires from [`ZCString::from_str_with_source`] (and therefore
+/// `From<&str>`), including when no source is set, and when deserializing
+/// strings that cannot be borrowed, such as JSON strings containing escapes.
+/// Useful for profiling or for enforcing zero-copy in tests.
+///
+/// ### Example
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use std::{cell::Cell, rc::Rc};
+/// # use arcstr::literal;
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, set_fallback_hook, ZCString};
+/// #[derive(Deserialize)]
+/// struct LogEntry {
+///     level: ZCString,
+///     message: ZCString,
+/// }
+///
+/// let misses = Rc::new(Cell::new(0));
+/// let counter = misses.clone();
+/// set_fallback_hook(Some(Box::new(move |_| counter.set(counter.get() + 1))));
+///
+/// let json = ZCString::from(literal!(r#"{"level": "error", "message": "Escaped \" "}"#));
+/// let entry: LogEntry = serde_json_from_zcstring(json).unwrap();
+/// set_fallback_hook(None);
+///
+/// // only the escaped message needed an allocation
+/// assert_eq!(entry.message, "Escaped \" ");
+/// assert_eq!(misses.get(), 1);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn set_fallback_hook(hook: Option<FallbackHook>) {
+    FALLBACK_HOOK.with(|ctx| *ctx.borrow_mut() = hook.map(Rc::from));
+}
+
+/// What to do when a string can't be borrowed from its source, set for a
+/// scope with [`ZCString::with_source_policy`].
+///
+/// The policy applies to strings created by [`ZCString::from_str_with_source`]
+/// (and therefore `From<&str>`) and by deserialization. The fallback hook
+/// set with [`set_fallback_hook`] is called under every policy.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub enum FallbackPolicy {
+    /// Copy the string into a new buffer, the default.
+    #[default]
+    Allocate,
+    /// Fail deserialization with a custom error rather than copying.
+    ///
+    /// Functions that can't fail, such as
+    /// [`ZCString::from_str_with_source`], still copy; use
+    /// [`ZCString::try_from_source`] where zero-copy must be guaranteed
+    /// outside of deserialization.
+    Strict,
+    /// Call the function with the string, then copy it.
+    Callback(Rc<dyn Fn(&str)>),
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for FallbackPolicy {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FallbackPolicy::Allocate => f.write_str("Allocate"),
+            FallbackPolicy::Strict => f.write_str("Strict"),
+            FallbackPolicy::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+// restores the policy replaced by with_source_policy, even on panic
+#[cfg(feature = "std")]
+struct PolicyGuard(FallbackPolicy);
+
+#[cfg(feature = "std")]
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        let policy = core::mem::take(&mut self.0);
+        FALLBACK_POLICY.with(|ctx| *ctx.borrow_mut() = policy);
+    }
+}
+
+/// Returns the innermost thread-local source of the current thread, if any.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{current_source, ZCString};
+/// let cats = ZCString::from("cats");
+/// let dogs = ZCString::from("dogs");
+/// assert_eq!(current_source(), None);
+///
+/// ZCString::with_source(cats, |cats| {
+///     assert!(current_source().unwrap().same_buffer(&cats));
+///
+///     ZCString::with_source(dogs, |dogs| {
+///         assert!(current_source().unwrap().same_buffer(&dogs));
+///     });
+///
+///     assert!(current_source().unwrap().same_buffer(&cats));
+/// });
+/// ```
+#[cfg(feature = "std")]
+pub fn current_source() -> Option<ZCString> {
+    SOURCE.with(|ctx| ctx.borrow().last().cloned())
+}
+
+/// Returns a [`SourceRef`] snapshot of the current thread-local sources, for
+/// converting many strings without a thread-local lookup per string.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{source_handle, ZCString};
+/// let source = ZCString::from("cat dog frog ".repeat(1000));
+///
+/// let (batched, single) = ZCString::with_source(source.clone(), |source| {
+///     let handle = source_handle();
+///     let batched: Vec<ZCString> = source.split(' ').map(|w| handle.convert(w)).collect();
+///     let single: Vec<ZCString> = source.split(' ').map(ZCString::from).collect();
+///     (batched, single)
+/// });
+///
+/// // same results as converting one at a time
+/// assert_eq!(batched, single);
+/// for (a, b) in batched.iter().zip(&single) {
+///     assert_eq!(source.source_of(a), source.source_of(b));
+/// }
+///
+/// // the handle keeps working after the guard is gone
+/// let handle = ZCString::with_source(source.clone(), |_| source_handle());
+/// assert!(source.source_of(&handle.convert(&source[4..7])));
+/// ```
+#[cfg(feature = "std")]
+pub fn source_handle() -> SourceRef {
+    SourceRef {
+        sources: SOURCE.with(|ctx| ctx.borrow().clone()),
+    }
+}
+
+/// Returns `true` if any thread-local source is installed on the current
+/// thread.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{has_source, ZCString};
+/// assert!(!has_source());
+///
+/// let guard = ZCString::from("cats").get_source_guard();
+/// assert!(has_source());
+///
+/// drop(guard);
+/// assert!(!has_source());
+/// ```
+#[cfg(feature = "std")]
+pub fn has_source() -> bool {
+    SOURCE.with(|ctx| !ctx.borrow().is_empty())
+}
+
+/// Returns `true` if `s` lies within one of the current thread-local
+/// sources, that is if [`ZCString::from_str_with_source`] would create it
+/// zero-copy.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{source_contains, ZCString};
+/// let source = ZCString::from("cats and dogs");
+///
+/// ZCString::with_source(source, |source| {
+///     assert!(source_contains(&source[5..8]));
+///     assert!(!source_contains("and"));
+/// });
+///
+/// assert!(!source_contains("and"));
+/// ```
+#[cfg(feature = "std")]
+pub fn source_contains(s: &str) -> bool {
+    SOURCE.with(|ctx| ctx.borrow().iter().any(|source| source.source_of(s)))
+}
+
+/// Removes all thread-local sources of the current thread, returning the
+/// innermost one. Lets long-lived threads release a pinned buffer early.
+///
+/// Clearing is not undone when guards installed before are dropped,
+/// dropping a guard whose source was cleared does nothing. Sources
+/// installed after clearing are unaffected.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{clear_source, has_source, ZCString};
+/// let cats = ZCString::from("cats");
+/// let dogs = ZCString::from("dogs");
+///
+/// let cats_guard = cats.get_source_guard();
+/// assert!(clear_source().unwrap().same_buffer(&cats));
+/// assert!(!has_source());
+///
+/// let dogs_guard = dogs.get_source_guard();
+/// drop(cats_guard);
+/// assert!(has_source());
+///
+/// drop(dogs_guard);
+/// assert!(!has_source());
+/// assert_eq!(clear_source(), None);
+/// ```
+#[cfg(feature = "std")]
+pub fn clear_source() -> Option<ZCString> {
+    // drop the sources outside of the borrow
+    let mut sources = SOURCE.with(|ctx| core::mem::take(&mut *ctx.borrow_mut()));
+    sources.pop()
+}
+
+// allocate s, reporting the zero-copy miss to the fallback hook
+fn fallback_alloc(s: &str) -> ZCString {
+    // clone the hook so it may replace itself without a borrow conflict
+    #[cfg(feature = "std")]
+    if let Some(hook) = FALLBACK_HOOK.with(|ctx| ctx.borrow().clone()) {
+        hook(s);
+    }
+
+    #[cfg(feature = "std")]
+    if let FallbackPolicy::Callback(callback) = FALLBACK_POLICY.with(|ctx| ctx.borrow().clone()) {
+        callback(s);
+    }
+
+    #[cfg(feature = "std")]
+    stats::record_miss(s.len());
+
+    ZCString::from_str_without_source(s)
+}
+
+// fallback_alloc for deserializers, failing under FallbackPolicy::Strict
+#[cfg(feature = "serde")]
+fn fallback_de<E: serde::de::Error>(s: &str) -> Result<ZCString, E> {
+    #[cfg(feature = "std")]
+    if FALLBACK_POLICY.with(|ctx| matches!(*ctx.borrow(), FallbackPolicy::Strict)) {
+        return Err(E::custom(format!(
+            "string of {} bytes can't be borrowed from the source and the fallback policy is strict",
+            s.len()
+        )));
+    }
+
+    Ok(fallback_alloc(s))
 }
 
 // error for File, Read and Seek operations
@@ -77,16 +418,90 @@ pub enum ReaderError {
     #[error("Invalid range: start {start} is greater than end {end}")]
     InvalidRange { start: u64, end: u64 },
 
+    #[error("Input too large: {size} bytes exceeds the limit of {limit} bytes")]
+    TooLarge { size: u64, limit: u64 },
+
     #[error("IO failure: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("UTF-8 encoding failure: {0}")]
-    Utf8(#[from] std::str::Utf8Error),
+    Utf8(#[from] core::str::Utf8Error),
+
+    #[error("Input starts with a UTF-16 byte order mark")]
+    Utf16Bom,
+
+    #[cfg(any(feature = "flate2", feature = "zstd"))]
+    #[error("Compression format {0:?} is not enabled")]
+    UnsupportedCodec(Codec),
+}
+
+/// Error for the whole read-then-parse flow, combining [`ReaderError`],
+/// UTF-8 validation and parser errors.
+///
+/// ```
+/// # use zcstring::{ReaderError, ZCError};
+/// let e: ZCError = ReaderError::InvalidRange { start: 2, end: 1 }.into();
+/// assert!(matches!(e, ZCError::Reader(ReaderError::InvalidRange { .. })));
+///
+/// // IO errors are reported as reader errors
+/// let e: ZCError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+/// assert!(matches!(e, ZCError::Reader(ReaderError::Io(_))));
+///
+/// let e: ZCError = std::str::from_utf8(&[0xff]).unwrap_err().into();
+/// assert!(matches!(e, ZCError::Utf8(_)));
+/// assert!(e.to_string().starts_with("UTF-8 encoding failure"));
+///
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// let e: ZCError = serde_json::from_str::<u32>("cat").unwrap_err().into();
+/// assert!(matches!(e, ZCError::Json(_)));
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum ZCError {
+    #[error("Read failure: {0}")]
+    Reader(#[from] ReaderError),
+
+    #[error("UTF-8 encoding failure: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+
+    #[cfg(feature = "serde_json")]
+    #[error("JSON failure: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "toml")]
+    #[error("TOML failure: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ZCError {
+    fn from(e: std::io::Error) -> Self {
+        ZCError::Reader(ReaderError::Io(e))
+    }
 }
 
 /// ZCString wrapper struct
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde_json", derive(Serialize))]
+///
+/// Compares by string contents against `str`, `String`, [`ArcStr`] and
+/// [`Substr`].
+///
+/// ```
+/// # use arcstr::{literal, ArcStr, Substr};
+/// # use zcstring::ZCString;
+/// let zc = ZCString::from("cat");
+/// let arc: ArcStr = literal!("cat");
+/// let sub = Substr::from(literal!("dog"));
+///
+/// assert_eq!(zc, arc);
+/// assert_eq!(arc, zc);
+/// assert_ne!(zc, sub);
+/// assert!(zc < sub);
+/// assert!(sub > zc);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ZCString(Substr);
 
 impl ZCString {
@@ -95,6 +510,32 @@ impl ZCString {
         ZCString::from(literal!(""))
     }
 
+    /// Returns a [`ZCStringBuilder`] able to hold `capacity` bytes before
+    /// reallocating, the `ZCString` analog of `String::with_capacity`.
+    ///
+    /// An `ArcStr` can't grow, so text is accumulated in the builder and
+    /// copied once into an exactly sized buffer by
+    /// [`ZCStringBuilder::build`]. Unused capacity is never retained.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let mut builder = ZCString::with_capacity(64);
+    /// assert!(builder.capacity() >= 64);
+    ///
+    /// for animal in ["cats", "dogs", "frogs"] {
+    ///     builder.push_str(animal);
+    ///     builder.push(' ');
+    /// }
+    ///
+    /// let zc = builder.build();
+    /// assert_eq!(zc, "cats dogs frogs ");
+    /// assert_eq!(zc.parent().len(), zc.len());
+    /// ```
+    pub fn with_capacity(capacity: usize) -> ZCStringBuilder {
+        ZCStringBuilder::with_capacity(capacity)
+    }
+
     /// Create an independent allocated copy of the underlying string
     /// buffer detached from the original string buffer.
     ///
@@ -111,454 +552,4814 @@ impl ZCString {
         ZCString::from_str_without_source(self.as_str())
     }
 
-    /// Returns `true` if the string slice `s` physically resides within the
-    /// memory bounds of this `ZCString`.
+    /// Slices `range` out of this string into a new, compact allocation
+    /// detached from the original buffer. Same as `substr(range).detach()`
+    /// without the intermediate sub-slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or not on char boundaries, like
+    /// [`Self::substr`].
     ///
-    /// ### Example
     /// ```
     /// # use zcstring::ZCString;
-    /// let root = ZCString::from_str_without_source("hello world");
-    /// let sub = &root[0..5];
-    /// assert!(root.source_of(sub));
+    /// let large = ZCString::from("cats and dogs");
+    /// let result = large.detach_range(5..8);
+    ///
+    /// assert_eq!(result, "and");
+    /// assert!(!large.source_of(&result));
     /// ```
-    pub fn source_of(&self, s: &str) -> bool {
-        if let Some(offset) = (s.as_ptr() as usize).checked_sub(self.0.as_ptr() as usize) {
-            // do we fall within?
-            offset < self.0.len()
-        } else {
-            // we fall below the source
-            false
-        }
-    }
+    pub fn detach_range(&self, range: impl RangeBounds<usize>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => *s + 1,
+            Bound::Unbounded => 0,
+        };
 
-    /// Creates a `ZCString` that uses a substr of the
-    /// current `ZCString` if possible, otherwise allocate
-    pub fn from_substr(&self, s: &str) -> Self {
-        match (s.as_ptr() as usize).checked_sub(self.0.as_ptr() as usize) {
-            Some(offset) if offset < self.0.len() => self.substr(offset..offset + s.len()),
-            _ => ZCString::from_str_without_source(s),
-        }
-    }
+        let end = match range.end_bound() {
+            Bound::Included(e) => *e + 1,
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => self.len(),
+        };
 
-    /// Creates a `ZCString` by allocating a new `ArcStr`.
-    ///
-    /// This bypasses the thread-local source check and just allocates.
-    pub fn from_str_without_source(s: &str) -> Self {
-        ZCString(Substr::from(ArcStr::from(s)))
+        ZCString::from_str_without_source(&self.as_str()[start..end])
     }
 
-    /// Creates a `ZCString` by checking if `s` is a sub-slice of the current
-    /// thread-local `SOURCE`.
+    /// Copies the entire backing buffer of this string, not just the viewed
+    /// range, into a new standalone `ArcStr`. Views of the old buffer can
+    /// then be moved onto the copy with [`Self::rebase`], after which the
+    /// old buffer is freed once nothing else refers to it.
     ///
-    /// If `s` is found within the source, it returns a pointer-based sub-slice.
-    /// Otherwise, it falls back to [`Self::from_str_without_source`].
-    pub fn from_str_with_source(s: &str) -> Self {
-        SOURCE.with(|ctx| match ctx.borrow().as_ref() {
-            Some(source) => source.from_substr(s),
-            None => ZCString::from_str_without_source(s),
-        })
-    }
-
-    /// Returns a sub-slice of this `ZCString` as a new `ZCString`.
-    pub fn substr(&self, range: impl RangeBounds<usize>) -> Self {
-        ZCString(self.0.substr(range))
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let dogs = source.substr(9..);
+    ///
+    /// let parent = dogs.compact_parent();
+    /// assert_eq!(parent, "cats and dogs");
+    /// assert!(!source.same_buffer(&parent.clone().into()));
+    /// ```
+    pub fn compact_parent(&self) -> ArcStr {
+        ArcStr::from(self.0.parent().as_str())
     }
 
-    /// Returns an RAII [`SourceGuard`] that sets this string as the thread-local
-    /// source. When the guard is dropped, the previous source is restored.
-    pub fn get_source_guard(&self) -> SourceGuard {
-        let mut source = Some(self.clone());
+    /// Returns a view of the same range of `new_parent` as this string is of
+    /// its own backing buffer, or `None` if `new_parent` differs in length
+    /// or the range's contents differ.
+    ///
+    /// Intended for moving many views onto a buffer returned by
+    /// [`Self::compact_parent`]. Only the viewed range is compared, so the
+    /// check costs as much as comparing the strings themselves.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let views: Vec<ZCString> = source.split(' ').map(|w| source.from_substr(w)).collect();
+    ///
+    /// let parent = views[0].compact_parent();
+    /// let rebased: Vec<ZCString> = views.iter().map(|v| v.rebase(&parent).unwrap()).collect();
+    ///
+    /// assert_eq!(rebased, views);
+    /// for (old, new) in views.iter().zip(&rebased) {
+    ///     assert_eq!(old.range(), new.range());
+    ///     assert!(!old.same_buffer(new));
+    ///     assert!(new.is_view_of(&ZCString::from(parent.clone())));
+    /// }
+    ///
+    /// // the old buffer goes away with the last view of it
+    /// drop(views);
+    /// drop(source);
+    /// assert_eq!(rebased, ["cats", "and", "dogs"]);
+    ///
+    /// // a buffer with different contents is rejected
+    /// assert_eq!(rebased[0].rebase(&"dogs and cats".into()), None);
+    /// ```
+    pub fn rebase(&self, new_parent: &ArcStr) -> Option<ZCString> {
+        let range = self.0.range();
 
-        SOURCE.with(|ctx| {
-            let mut borrow = ctx.borrow_mut();
-            std::mem::swap(&mut *borrow, &mut source);
-        });
+        if new_parent.len() != self.0.parent().len()
+            || new_parent.as_bytes()[range.clone()] != *self.as_bytes()
+        {
+            return None;
+        }
 
-        SourceGuard { old_source: source }
+        Some(ZCString(new_parent.substr(range)))
     }
 
-    /// Executes a closure with this `ZCString` set as the thread-local source.
+    /// Returns `true` if the string slice `s` physically resides within the
+    /// memory bounds of this `ZCString`.
     ///
-    /// This is the preferred way to handle contextual string operations.
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let root = ZCString::from_str_without_source("hello world");
+    /// let sub = &root[0..5];
+    /// assert!(root.source_of(sub));
+    /// ```
+    pub fn source_of(&self, s: &str) -> bool {
+        self.offset_of(s).is_some()
+    }
+
+    /// Returns `true` if `self` is a zero-copy view into `source`, the same
+    /// as `source.source_of(self)`. See also [`assert_zero_copy!`].
     ///
     /// ### Example
     /// ```
     /// # use zcstring::ZCString;
-    /// let source = ZCString::from("1 23 456 789 0");
+    /// let source = ZCString::from("cats and dogs");
     ///
-    /// // Call a lambda function with our thread local storage
-    /// // set to zc
-    /// let result = ZCString::with_source(source, |source| {
-    ///     // make it clear we are working with an &str
-    ///     // borrowed from source
-    ///     let s: &str = &source;
-    ///     s
-    ///         .split(' ')
-    ///         // ZCString::from(v: &str) checks does &str lives in source?
-    ///         .map(|v| ZCString::from(v))
-    ///         // do we really point back to source?
-    ///         .for_each(|v| assert!(source.source_of(&v)));
-    /// });
+    /// assert!(source.substr(0..4).is_view_of(&source));
+    /// assert!(!ZCString::from("cats").is_view_of(&source));
     /// ```
-    pub fn with_source<F, R>(source: ZCString, f: F) -> R
-    where
-        F: FnOnce(ZCString) -> R,
-    {
-        let guard = source.get_source_guard();
-        let result = f(source);
-        drop(guard);
-        result
+    pub fn is_view_of(&self, source: &ZCString) -> bool {
+        source.source_of(self)
     }
 
-    /// Transforms the current [`ZCString`] into a new view using a closure,
-    /// provided the result is a sub-slice of the original.
-    ///
-    /// This is a high-level utility for performing zero-copy operations like
-    /// trimming or pattern-based slicing using standard [`str`] methods.
+    /// Returns `true` if `self` and `other` are slices of the same backing
+    /// `ArcStr`, regardless of whether the slices overlap.
     ///
+    /// Unlike `==` this ignores the contents, and unlike [`Self::source_of`]
+    /// it doesn't require one string to contain the other.
     ///
     /// ### Example
     /// ```
     /// # use zcstring::ZCString;
-    /// let zc = ZCString::from("  zero-copy  ");
+    /// let source = ZCString::from("cats and dogs");
+    /// let cats = source.substr(0..4);
+    /// let dogs = source.substr(9..);
     ///
-    /// // Use map to trim the string without new allocations
-    /// let trimmed = zc.map(|s| s.trim());
+    /// assert!(cats.same_buffer(&dogs));
+    /// assert!(!cats.source_of(&dogs));
+    /// assert!(!cats.same_buffer(&dogs.detach()));
     ///
-    /// assert_eq!(trimmed, "zero-copy");
+    /// // independently allocated, equal or not
+    /// let a = ZCString::from_str_without_source("cats");
+    /// let b = ZCString::from_str_without_source("cats");
+    /// assert_eq!(a, b);
+    /// assert!(!a.same_buffer(&b));
+    /// assert!(!a.same_buffer(&ZCString::from_str_without_source("dogs")));
     /// ```
-    pub fn map<F>(&self, f: F) -> ZCString
-    where
-        F: FnOnce(&str) -> &str,
-    {
-        self.from_substr(f(self))
+    pub fn same_buffer(&self, other: &ZCString) -> bool {
+        ArcStr::ptr_eq(self.0.parent(), other.0.parent())
     }
 
-    /// Wraps a standard string iterator to produce [`ZCString`] items instead of `&str`.
+    /// Returns a pointer to the start of the backing `ArcStr`, identifying
+    /// the buffer this string is a slice of. Useful as a key for grouping
+    /// strings by buffer; see [`Self::same_buffer`].
     ///
-    /// This method allows you to leverage existing [`str`] iteration logic (like `.lines()` or `.split()`)
-    /// while automatically promoting each yielded slice into a zero-copy [`ZCString`].
+    /// The pointer is only meaningful while the buffer is alive, as it may
+    /// be reused by a later allocation.
     ///
-    /// The resulting items share the same underlying [`arcstr::ArcStr`] as this source,
-    /// ensuring memory stays alive as long as any yielded item exists.
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
     ///
-    /// ### Arguments
-    /// * `f` - A closure that takes a reference to the inner string and returns an iterator yielding `&str`.
+    /// assert_eq!(source.substr(0..4).parent_ptr(), source.substr(9..).parent_ptr());
+    /// ```
+    pub fn parent_ptr(&self) -> *const u8 {
+        self.0.parent().as_ptr()
+    }
+
+    /// Creates a `ZCString` that uses a substr of the
+    /// current `ZCString` if possible, otherwise allocate
+    ///
+    /// Only slices lying entirely within this `ZCString` are zero-copy, a
+    /// slice starting inside but running past the end, or coming from an
+    /// unrelated allocation, is copied.
+    ///
+    /// The decision is made by address alone. That is sound because this
+    /// `ZCString` keeps its buffer alive, so no other live string can occupy
+    /// the same memory. A thread-local source is likewise held by its guard
+    /// for as long as it is installed. As a check on that reasoning, debug
+    /// builds also compare the resulting slice with `s` and copy `s` if they
+    /// differ.
     ///
     /// ### Example
     /// ```
     /// # use zcstring::ZCString;
-    /// let zc = ZCString::from("line1\nline2\nline3");
+    /// let buffer = ZCString::from_str_without_source("cats and dogs");
+    /// let root = buffer.substr(0..8);
+    /// let other = ZCString::from_str_without_source("cats and dogs");
     ///
-    /// // Wrap the standard .lines() iterator
-    /// let mut iter = zc.wrap_iter(|s| s.lines());
+    /// // within root
+    /// assert!(root.source_of(&root.from_substr(&root[5..8])));
     ///
-    /// assert_eq!(iter.next().unwrap(), "line1");
-    /// assert_eq!(iter.next().unwrap(), "line2");
+    /// // starts within root but runs past its end
+    /// let overlapping = root.from_substr(&buffer[5..13]);
+    /// assert_eq!(overlapping, "and dogs");
+    /// assert!(!root.source_of(&overlapping));
+    ///
+    /// // unrelated allocations, directly after root and elsewhere
+    /// for s in [&buffer[8..13], &other[0..4], "cats"] {
+    ///     let copied = root.from_substr(s);
+    ///     assert_eq!(copied, s);
+    ///     assert!(!root.source_of(&copied));
+    /// }
     /// ```
-    pub fn wrap_iter<'a, F, I>(&'a self, f: F) -> ZCStringIterWrapper<'a, I>
-    where
-        F: FnOnce(&'a str) -> I,
-        I: Iterator<Item = &'a str>,
-    {
-        ZCStringIterWrapper {
-            source: self.clone(),
-            inner: f(self.as_str()),
-            _marker: std::marker::PhantomData,
+    pub fn from_substr(&self, s: &str) -> Self {
+        match self.offset_of(s) {
+            Some(offset) => {
+                let result = self.substr(offset..offset + s.len());
+
+                // never trust the address check over the contents
+                if cfg!(debug_assertions) && result.as_str() != s {
+                    #[cfg(feature = "std")]
+                    stats::record_miss(s.len());
+                    return ZCString::from_str_without_source(s);
+                }
+
+                #[cfg(feature = "std")]
+                stats::record_hit();
+                result
+            }
+            None => {
+                #[cfg(feature = "std")]
+                stats::record_miss(s.len());
+                ZCString::from_str_without_source(s)
+            }
         }
     }
 
-    #[cfg(feature = "std")]
-    /// Create a ZCString by reading a range of bytes from a
-    /// an object supporting Read and Seek traits. The range must
-    /// contain valid UTF-8
+    /// Same as [`Self::from_substr`] but always compares the contents of the
+    /// resulting slice with `s`, in release builds too, copying `s` if they
+    /// differ.
     ///
-    /// ### Arguments
+    /// `from_substr` decides by address alone, which is sound as long as
+    /// every `&str` passed in is a valid borrow. This adds a `memcmp` of
+    /// `s.len()` bytes to every zero-copy call as defense in depth against
+    /// `unsafe` code handing in slices with the right address but the wrong
+    /// contents. Safe code can't produce such a slice, so in practice both
+    /// functions give the same result.
+    ///
+    /// ### Example
     /// ```
-    /// # use std::io::Cursor;
     /// # use zcstring::ZCString;
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// // test data in a form that supports Read & Seek traits
-    /// // as if coming from a File
-    /// let mut data = Cursor::new(b"Cats and dogs");
-    /// // read "and" from 'data'
-    /// let mut r = ZCString::read_range(&mut data, 5..8)?;
-    /// assert_eq!(r, "and");
-    /// # Ok(())
-    /// # }
+    /// let source = ZCString::from("cats and dogs");
+    ///
+    /// let and = source.from_substr_checked(&source[5..8]);
+    /// assert_eq!(and, "and");
+    /// assert!(source.source_of(&and));
+    ///
+    /// let copied = source.from_substr_checked("and");
+    /// assert_eq!(copied, "and");
+    /// assert!(!source.source_of(&copied));
     /// ```
-    pub fn read_range<I, R>(input: &mut I, range: R) -> Result<ZCString, ReaderError>
-    where
-        I: Read + Seek,
-        R: RangeBounds<u64>,
-    {
-        let start_pos = match range.start_bound() {
-            Bound::Included(s) => *s,
-            Bound::Excluded(s) => *s + 1,
-            Bound::Unbounded => input.stream_position()?,
-        };
+    pub fn from_substr_checked(&self, s: &str) -> Self {
+        match self.offset_of(s) {
+            Some(offset) if &self.as_bytes()[offset..offset + s.len()] == s.as_bytes() => {
+                #[cfg(feature = "std")]
+                stats::record_hit();
+                self.substr(offset..offset + s.len())
+            }
+            _ => {
+                #[cfg(feature = "std")]
+                stats::record_miss(s.len());
+                ZCString::from_str_without_source(s)
+            }
+        }
+    }
 
-        let end_pos = match range.end_bound() {
-            Bound::Included(e) => *e + 1,
-            Bound::Excluded(e) => *e,
-            Bound::Unbounded => input.seek(SeekFrom::End(0))?,
-        };
+    // byte offset of s within self, if s lies entirely within self
+    fn offset_of(&self, s: &str) -> Option<usize> {
+        // below the source?
+        let offset = (s.as_ptr() as usize).checked_sub(self.0.as_ptr() as usize)?;
 
-        if start_pos > end_pos {
-            // error
-            return Err(ReaderError::InvalidRange {
-                start: start_pos,
-                end: end_pos,
-            });
+        // do we start and end within?
+        if offset < self.0.len() && s.len() <= self.0.len() - offset {
+            Some(offset)
+        } else {
+            None
         }
+    }
 
-        if start_pos == end_pos {
-            // edge case
-            return Ok(ZCString::new());
-        }
+    /// Converts many sub-slices of this string at once, as by calling
+    /// [`Self::from_substr`] on each. Meant for bulk tokenizers, the bounds
+    /// of this string are computed once for the whole batch.
+    ///
+    /// Slices lying within this string become zero-copy views, any other
+    /// slice is copied. Each view still holds its own reference to the
+    /// shared buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let line = "cat,dog,frog,".repeat(10_000);
+    /// let zc = ZCString::from(line.as_str());
+    ///
+    /// let fields = zc.substrs_from(zc.split_terminator(','));
+    ///
+    /// assert_eq!(fields.len(), 30_000);
+    /// assert_eq!(fields[..4], ["cat", "dog", "frog", "cat"]);
+    /// assert!(fields.iter().all(|field| zc.source_of(field)));
+    ///
+    /// // slices from elsewhere are copied
+    /// let mixed = zc.substrs_from([&zc[0..3], "cat"]);
+    /// assert_eq!(mixed, ["cat", "cat"]);
+    /// assert!(zc.source_of(&mixed[0]) && !zc.source_of(&mixed[1]));
+    /// ```
+    pub fn substrs_from<'a, I>(&self, slices: I) -> Vec<ZCString>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let start = self.as_ptr() as usize;
+        let len = self.len();
 
-        let mut io_error = Ok(());
+        slices
+            .into_iter()
+            .map(|s| match (s.as_ptr() as usize).checked_sub(start) {
+                Some(offset) if offset < len && s.len() <= len - offset => {
+                    self.substr(offset..offset + s.len())
+                }
+                _ => ZCString::from_str_without_source(s),
+            })
+            .collect()
+    }
 
-        let result = ArcStr::init_with((end_pos - start_pos) as usize, |buffer| {
-            io_error = (|| -> Result<(), std::io::Error> {
-                input.seek(SeekFrom::Start(start_pos))?;
-                input.read_exact(buffer)?;
-                Ok(())
-            })()
-        })?;
+    /// Creates a `ZCString` by allocating a new `ArcStr`.
+    ///
+    /// This bypasses the thread-local source check and just allocates.
+    pub fn from_str_without_source(s: &str) -> Self {
+        ZCString(Substr::from(ArcStr::from(s)))
+    }
 
-        match io_error {
-            Ok(()) => Ok(ZCString::from(result)),
-            Err(e) => Err(e)?,
-        }
+    #[cfg(feature = "bytes")]
+    /// Creates a `ZCString` from a `bytes::Bytes`, such as an HTTP body.
+    ///
+    /// The bytes are validated as UTF-8 in place and then copied once into a
+    /// new buffer, `ArcStr` can't share the `Bytes` allocation.
+    ///
+    /// **Requires the `bytes` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use bytes::Bytes;
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::try_from_bytes(Bytes::from_static(b"cats")).unwrap();
+    /// assert_eq!(zc, "cats");
+    ///
+    /// assert_eq!(ZCString::try_from_bytes(Bytes::new()).unwrap(), "");
+    /// assert!(ZCString::try_from_bytes(Bytes::from_static(b"cat\xff")).is_err());
+    /// ```
+    pub fn try_from_bytes(bytes: bytes::Bytes) -> Result<Self, core::str::Utf8Error> {
+        Ok(ZCString::from_str_without_source(core::str::from_utf8(
+            &bytes,
+        )?))
     }
 
-    #[cfg(feature = "std")]
-    /// Create a ZCString by reading bytes from an object supporting the Read trait.
-    /// The bytes must be valid UTF-8
+    #[cfg(feature = "bytes")]
+    /// Same as [`Self::try_from_bytes`] but replaces invalid UTF-8 sequences
+    /// with `U+FFFD`, as `String::from_utf8_lossy` does.
     ///
-    /// ### Arguments
+    /// **Requires the `bytes` feature.**
+    ///
+    /// ### Example
     /// ```
-    /// # use std::io::Cursor;
+    /// # use bytes::Bytes;
     /// # use zcstring::ZCString;
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// // test data in a form that supports Read & Seek traits
-    /// // as if coming from a File
-    /// let mut data = Cursor::new(b"Cats and dogs");
-    /// // read "and" from 'data'
-    /// let mut r = ZCString::read(&mut data, 4)?;
-    /// assert_eq!(r, "Cats");
-    /// # Ok(())
-    /// # }
+    /// let zc = ZCString::from_bytes_lossy(Bytes::from_static(b"cat\xff"));
+    /// assert_eq!(zc, "cat\u{fffd}");
     /// ```
-    pub fn read<I: Read>(input: &mut I, bytes: usize) -> Result<ZCString, ReaderError> {
-        let mut io_error = Ok(());
+    pub fn from_bytes_lossy(bytes: bytes::Bytes) -> Self {
+        ZCString::from_str_without_source(&String::from_utf8_lossy(&bytes))
+    }
 
-        let result = ArcStr::init_with(bytes, |buffer| {
-            io_error = (|| -> Result<(), std::io::Error> {
-                input.read_exact(buffer)?;
-                Ok(())
-            })()
-        })?;
+    /// Creates a `ZCString` from bytes that should be UTF-8, replacing any
+    /// invalid sequences with `U+FFFD` as `String::from_utf8_lossy` does.
+    ///
+    /// Valid bytes go through [`Self::from_str_with_source`], so they are
+    /// zero-copy if they lie within the current source. Otherwise a new
+    /// buffer is allocated for the repaired string.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    ///
+    /// # #[cfg(feature = "std")]
+    /// ZCString::with_source(source.clone(), |source| {
+    ///     let clean = ZCString::from_utf8_lossy(&source.as_bytes()[5..8]);
+    ///     assert_eq!(clean, "and");
+    ///     assert!(source.source_of(&clean));
+    /// });
+    ///
+    /// let repaired = ZCString::from_utf8_lossy(b"cat\xffdog");
+    /// assert_eq!(repaired, "cat\u{fffd}dog");
+    /// ```
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        match String::from_utf8_lossy(bytes) {
+            alloc::borrow::Cow::Borrowed(s) => ZCString::from_str_with_source(s),
+            alloc::borrow::Cow::Owned(s) => ZCString::from_str_without_source(&s),
+        }
+    }
 
-        match io_error {
-            Ok(()) => Ok(ZCString::from(result)),
-            Err(e) => Err(e)?,
+    /// Creates a `ZCString` by checking if `s` is a sub-slice of the current
+    /// thread-local `SOURCE`.
+    ///
+    /// If `s` is found within the source, it returns a pointer-based sub-slice.
+    /// Otherwise, it falls back to [`Self::from_str_without_source`]. Without
+    /// the `std` feature there is no thread-local source, so it always
+    /// allocates.
+    pub fn from_str_with_source(s: &str) -> Self {
+        #[cfg(feature = "std")]
+        if let Some(result) = ZCString::try_from_source(s) {
+            return result;
         }
+
+        fallback_alloc(s)
     }
 
-    #[cfg(feature = "std")]
-    /// Create a ZCString by reading an entire file
+    #[cfg(feature = "serde_json")]
+    /// Parses this string as JSON into type `T`, using it as the source for
+    /// zero-copy deserialization. Same as [`serde_json_from_zcstring`].
     ///
-    /// ### Arguments
+    /// ### Example
     /// ```
+    /// # use serde::Deserialize;
     /// # use zcstring::ZCString;
-    /// # use std::path::PathBuf;
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// // Construct path relative to the project root
-    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    /// path.push("examples");
-    /// path.push("from_file_test.txt");
-    /// let r = ZCString::from_file(path)?;
-    /// assert_eq!(&r, "xyzzy");
-    /// # Ok(())
-    /// # }
+    /// #[derive(Deserialize)]
+    /// struct Animal {
+    ///     animal: ZCString,
+    /// }
+    ///
+    /// let json = ZCString::from(r#"{"animal": "cat"}"#);
+    /// let parsed = json.clone().parse_json::<Animal>().unwrap();
+    ///
+    /// assert_eq!(parsed.animal, "cat");
+    /// assert!(json.source_of(&parsed.animal));
     /// ```
-    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<ZCString, ReaderError> {
-        let mut handle = std::fs::File::open(path)?;
-        Self::read_range(&mut handle, 0..)
+    pub fn parse_json<T>(self) -> Result<T, serde_json::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json_from_zcstring(self)
     }
-}
 
-impl Default for ZCString {
-    fn default() -> Self {
-        ZCString::from(literal!(""))
+    #[cfg(feature = "std")]
+    /// Creates a `ZCString` only if `s` lies within the current thread-local
+    /// source, guaranteeing zero-copy. Returns `None` where
+    /// [`Self::from_str_with_source`] would allocate.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let foreign = String::from("cats");
+    ///
+    /// ZCString::with_source(source, |source| {
+    ///     let cats = ZCString::try_from_source(&source[0..4]).unwrap();
+    ///     assert!(source.source_of(&cats));
+    ///
+    ///     assert_eq!(ZCString::try_from_source(&foreign), None);
+    /// });
+    ///
+    /// // no source active
+    /// assert_eq!(ZCString::try_from_source("cats"), None);
+    /// ```
+    pub fn try_from_source(s: &str) -> Option<Self> {
+        // slice within the borrow, saving a clone of the source;
+        // from_substr doesn't touch SOURCE so this can't re-enter it
+        SOURCE.with(|ctx| {
+            ctx.borrow()
+                .iter()
+                .rev()
+                .find(|source| source.source_of(s))
+                .map(|source| source.from_substr(s))
+        })
     }
-}
 
-impl PartialEq<str> for ZCString {
-    fn eq(&self, other: &str) -> bool {
-        self.0 == other
+    /// Creates a `ZCString` from a raw pointer and length, as by
+    /// [`Self::from_str_with_source`]: zero-copy if the bytes lie within the
+    /// current thread-local source, otherwise copied.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null and valid for reads of `len` bytes, the bytes
+    /// must lie within a single live allocation and be valid UTF-8. See
+    /// [`std::slice::from_raw_parts`] and [`std::str::from_utf8_unchecked`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let (ptr, len) = (source[5..].as_ptr(), 3);
+    ///
+    /// # #[cfg(feature = "std")]
+    /// ZCString::with_source(source, |source| {
+    ///     let and = unsafe { ZCString::from_raw_parts(ptr, len) };
+    ///     assert_eq!(and, "and");
+    ///     assert!(source.source_of(&and));
+    /// });
+    /// ```
+    pub unsafe fn from_raw_parts(ptr: *const u8, len: usize) -> Self {
+        let s = core::str::from_utf8_unchecked(core::slice::from_raw_parts(ptr, len));
+        ZCString::from_str_with_source(s)
     }
-}
 
-impl PartialEq<&str> for ZCString {
-    fn eq(&self, other: &&str) -> bool {
-        self.0 == *other
+    /// Returns a sub-slice of this `ZCString` as a new `ZCString`.
+    pub fn substr(&self, range: impl RangeBounds<usize>) -> Self {
+        ZCString(self.0.substr(range))
     }
-}
 
-impl PartialEq<ZCString> for &str {
-    fn eq(&self, other: &ZCString) -> bool {
-        self == &**other
+    /// Same as [`Self::substr`] but with `range` given in characters rather
+    /// than bytes. Returns `None` if the range runs past the end of the
+    /// string or its start lies after its end.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("αβγδ");
+    ///
+    /// let middle = zc.substr_chars(1..3).unwrap();
+    /// assert_eq!(middle, "βγ");
+    /// assert!(zc.source_of(&middle));
+    ///
+    /// assert_eq!(zc.substr_chars(..=1).unwrap(), "αβ");
+    /// assert_eq!(zc.substr_chars(3..).unwrap(), "δ");
+    /// assert_eq!(zc.substr_chars(4..).unwrap(), "");
+    /// assert_eq!(zc.substr_chars(2..5), None);
+    /// assert_eq!(zc.substr_chars(3..2), None);
+    /// ```
+    pub fn substr_chars(&self, range: impl RangeBounds<usize>) -> Option<Self> {
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        // byte offset of every char boundary, including the end
+        let mut boundaries = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()));
+
+        let start_byte = boundaries.nth(start)?;
+        let end_byte = match range.end_bound() {
+            Bound::Included(e) => boundaries.nth(e.checked_sub(start)?)?,
+            Bound::Excluded(e) if *e == start => start_byte,
+            Bound::Excluded(e) => boundaries.nth(e.checked_sub(start)?.checked_sub(1)?)?,
+            Bound::Unbounded => self.len(),
+        };
+
+        Some(self.substr(start_byte..end_byte))
     }
-}
 
-#[cfg(feature = "std")]
-impl PartialEq<String> for ZCString {
-    fn eq(&self, other: &String) -> bool {
-        self.0 == *other
+    #[cfg(feature = "std")]
+    /// Returns an RAII [`SourceGuard`] that pushes this string onto the
+    /// thread-local stack of sources. When the guard is dropped the string is
+    /// removed again.
+    ///
+    /// Sources installed earlier stay active underneath, strings are looked
+    /// up from the most recently installed source down. Guards may be
+    /// dropped in any order, each removes only its own entry.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let cats = ZCString::from("cats");
+    /// let dogs = ZCString::from("dogs");
+    ///
+    /// let cats_guard = cats.get_source_guard();
+    /// let dogs_guard = dogs.get_source_guard();
+    ///
+    /// // dropped out of order, dogs stays active
+    /// drop(cats_guard);
+    /// assert!(!cats.source_of(&ZCString::from(&cats[..])));
+    /// assert!(dogs.source_of(&ZCString::from(&dogs[..])));
+    ///
+    /// drop(dogs_guard);
+    /// assert!(!dogs.source_of(&ZCString::from(&dogs[..])));
+    /// ```
+    pub fn get_source_guard(&self) -> SourceGuard {
+        let replaced = SOURCE.with(|ctx| {
+            let mut stack = ctx.borrow_mut();
+            let replaced = stack.last().cloned();
+            stack.push(self.clone());
+            replaced
+        });
+
+        SourceGuard {
+            source: self.clone(),
+            replaced,
+            _not_send: core::marker::PhantomData,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Executes a closure with this `ZCString` set as the thread-local source.
+    ///
+    /// This is the preferred way to handle contextual string operations.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("1 23 456 789 0");
+    ///
+    /// // Call a lambda function with our thread local storage
+    /// // set to zc
+    /// let result = ZCString::with_source(source, |source| {
+    ///     // make it clear we are working with an &str
+    ///     // borrowed from source
+    ///     let s: &str = &source;
+    ///     s
+    ///         .split(' ')
+    ///         // ZCString::from(v: &str) checks does &str lives in source?
+    ///         .map(|v| ZCString::from(v))
+    ///         // do we really point back to source?
+    ///         .for_each(|v| assert!(source.source_of(&v)));
+    /// });
+    /// ```
+    ///
+    /// Calls may be nested, for example by a `Deserialize` impl that parses
+    /// an embedded document with a source of its own. Sources stack, while
+    /// the inner call runs slices of the outer source are still zero-copy.
+    ///
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let outer = ZCString::from("outer cats");
+    /// let inner = ZCString::from("inner dogs");
+    ///
+    /// ZCString::with_source(outer, |outer| {
+    ///     ZCString::with_source(inner, |inner| {
+    ///         let dogs = ZCString::from(&inner[6..]);
+    ///         let cats = ZCString::from(&outer[6..]);
+    ///
+    ///         assert!(inner.source_of(&dogs));
+    ///         assert!(outer.source_of(&cats));
+    ///     });
+    /// });
+    /// ```
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde_json")]
+    /// # {
+    /// # use serde::{Deserialize, Deserializer};
+    /// # use zcstring::{serde_json_from_zcstring, ZCString};
+    /// #[derive(Deserialize)]
+    /// struct Inner {
+    ///     name: ZCString,
+    /// }
+    ///
+    /// // the payload is JSON embedded as a string
+    /// struct Payload(Inner);
+    ///
+    /// impl<'de> Deserialize<'de> for Payload {
+    ///     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    ///         let text = ZCString::deserialize(d)?;
+    ///         serde_json_from_zcstring(text)
+    ///             .map(Payload)
+    ///             .map_err(serde::de::Error::custom)
+    ///     }
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Outer {
+    ///     payload: Payload,
+    ///     kind: ZCString,
+    /// }
+    ///
+    /// let json = ZCString::from(r#"{"payload": "{\"name\": \"cat\"}", "kind": "animal"}"#);
+    /// let outer: Outer = serde_json_from_zcstring(json.clone()).unwrap();
+    ///
+    /// assert_eq!(outer.payload.0.name, "cat");
+    /// // the outer source is still active after the nested parse
+    /// assert!(json.source_of(&outer.kind));
+    /// # }
+    /// ```
+    pub fn with_source<F, R>(source: ZCString, f: F) -> R
+    where
+        F: FnOnce(ZCString) -> R,
+    {
+        let guard = source.get_source_guard();
+        let result = f(source);
+        drop(guard);
+        result
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::with_source`] but borrows the source, so it remains
+    /// usable afterwards without cloning it first.
+    ///
+    /// Strings created inside `f` hold their own reference to the buffer,
+    /// they may be returned and stay valid after the source is removed.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    ///
+    /// let words: Vec<ZCString> = source.with_source_ref(|source| {
+    ///     source.split(' ').map(ZCString::from).collect()
+    /// });
+    ///
+    /// // still usable, and the words still point into it
+    /// assert!(words.iter().all(|word| source.source_of(word)));
+    /// drop(source);
+    /// assert_eq!(words, ["cats", "and", "dogs"]);
+    /// ```
+    pub fn with_source_ref<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&ZCString) -> R,
+    {
+        let _guard = self.get_source_guard();
+        f(self)
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::with_source`] for closures returning a `Result`, so
+    /// `?` can be used inside the closure and the error is passed straight
+    /// through.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # use std::num::ParseIntError;
+    /// let source = ZCString::from("1 23 cat");
+    ///
+    /// let sum = ZCString::try_with_source(source.clone(), |source| {
+    ///     let mut sum = 0;
+    ///     for word in source.split(' ').take(2) {
+    ///         sum += ZCString::from(word).parse_to::<u32>()?;
+    ///     }
+    ///     Ok::<_, ParseIntError>(sum)
+    /// });
+    /// assert_eq!(sum, Ok(24));
+    ///
+    /// let failed = ZCString::try_with_source(source, |source| {
+    ///     source.split(' ').map(|word| word.parse::<u32>()).sum::<Result<u32, _>>()
+    /// });
+    /// assert!(failed.is_err());
+    /// ```
+    pub fn try_with_source<F, T, E>(source: ZCString, f: F) -> Result<T, E>
+    where
+        F: FnOnce(ZCString) -> Result<T, E>,
+    {
+        ZCString::with_source(source, f)
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::with_source`] with `policy` deciding what happens to
+    /// strings that can't be borrowed from the source while `f` runs. The
+    /// previous policy is restored afterwards.
+    ///
+    /// ### Example
+    /// ```
+    /// # #[cfg(feature = "serde_json")]
+    /// # {
+    /// # use std::{cell::RefCell, rc::Rc};
+    /// # use serde::Deserialize;
+    /// # use zcstring::{FallbackPolicy, ZCString};
+    /// #[derive(Debug, Deserialize)]
+    /// struct LogEntry {
+    ///     level: ZCString,
+    ///     message: ZCString,
+    /// }
+    ///
+    /// let parse = |json: &str, policy| {
+    ///     ZCString::with_source_policy(ZCString::from(json), policy, |json| {
+    ///         serde_json::from_str::<LogEntry>(&json)
+    ///     })
+    /// };
+    ///
+    /// let clean = r#"{"level": "error", "message": "Connection lost"}"#;
+    /// let escaped = r#"{"level": "error", "message": "Escaped \" "}"#;
+    ///
+    /// // strict: escapes can't be borrowed, so deserialization fails
+    /// assert!(parse(clean, FallbackPolicy::Strict).is_ok());
+    /// assert!(parse(escaped, FallbackPolicy::Strict).is_err());
+    ///
+    /// // callback: the escaped string is reported, then copied
+    /// let misses = Rc::new(RefCell::new(Vec::new()));
+    /// let log = misses.clone();
+    /// let callback = FallbackPolicy::Callback(Rc::new(move |s| log.borrow_mut().push(s.to_string())));
+    ///
+    /// let entry = parse(escaped, callback).unwrap();
+    /// assert_eq!(entry.message, "Escaped \" ");
+    /// assert_eq!(*misses.borrow(), ["Escaped \" "]);
+    ///
+    /// // the default policy is back in place
+    /// assert!(ZCString::with_source(ZCString::from(escaped), |json| {
+    ///     serde_json::from_str::<LogEntry>(&json)
+    /// })
+    /// .is_ok());
+    /// # }
+    /// ```
+    pub fn with_source_policy<F, R>(source: ZCString, policy: FallbackPolicy, f: F) -> R
+    where
+        F: FnOnce(ZCString) -> R,
+    {
+        let previous = FALLBACK_POLICY.with(|ctx| ctx.replace(policy));
+        let _restore = PolicyGuard(previous);
+        ZCString::with_source(source, f)
+    }
+
+    #[cfg(feature = "std")]
+    /// Runs the future `fut` with `source` as its source, scoped to the
+    /// task rather than the thread.
+    ///
+    /// The thread-local source is installed only for the duration of each
+    /// poll of `fut` and removed again in between, so it never
+    /// leaks into other tasks sharing the thread across `.await` points, nor
+    /// is it lost when the task resumes on a different worker thread. Works
+    /// with any executor.
+    ///
+    /// Within async code use this instead of [`Self::with_source`] or a
+    /// [`SourceGuard`] whenever the source must stay active across an
+    /// `.await`, the thread-local forms are only safe for synchronous work
+    /// between awaits.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # rt.block_on(async {
+    /// async fn words(source: ZCString) -> Vec<ZCString> {
+    ///     let mut words = Vec::new();
+    ///     for word in source.split(' ') {
+    ///         // let the other task run on this thread in between
+    ///         tokio::task::yield_now().await;
+    ///         words.push(ZCString::from(word));
+    ///     }
+    ///     words
+    /// }
+    ///
+    /// let cats = ZCString::from("cats and more cats");
+    /// let dogs = ZCString::from("dogs and more dogs");
+    ///
+    /// let (cat_words, dog_words) = tokio::join!(
+    ///     ZCString::with_source_async(cats.clone(), words(cats.clone())),
+    ///     ZCString::with_source_async(dogs.clone(), words(dogs.clone())),
+    /// );
+    ///
+    /// assert!(cat_words.iter().all(|w| cats.source_of(w)));
+    /// assert!(dog_words.iter().all(|w| dogs.source_of(w)));
+    /// # });
+    /// ```
+    pub async fn with_source_async<F: core::future::Future>(source: ZCString, fut: F) -> F::Output {
+        let mut fut = core::pin::pin!(fut);
+
+        core::future::poll_fn(|cx| {
+            let _guard = source.get_source_guard();
+            fut.as_mut().poll(cx)
+        })
+        .await
+    }
+
+    /// Returns `true` if this string starts with `prefix`.
+    ///
+    /// Accepts anything implementing `AsRef<str>` so a `ZCString` or
+    /// `String` can be passed without borrowing it as `&str` first.
+    ///
+    /// Named `starts_with_zc` rather than `starts_with` so the pattern based
+    /// `str::starts_with` stays reachable through `Deref`. That one can't take a
+    /// `&ZCString`, `str`'s `Pattern` trait is unstable, so
+    /// `zc.starts_with(&other)` doesn't compile, pass `other.as_str()` instead.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats and dogs");
+    /// let cats = ZCString::from("cats");
+    ///
+    /// assert!(zc.starts_with_zc(&cats) && zc.starts_with_zc(cats.clone()));
+    /// assert!(zc.starts_with_zc(String::from("cats")));
+    /// assert!(!zc.starts_with_zc("dogs"));
+    /// assert!(zc.starts_with(cats.as_str()));
+    ///
+    /// // str patterns through deref
+    /// assert!(zc.starts_with('c'));
+    /// assert!(zc.starts_with(char::is_alphabetic));
+    /// assert!(zc.starts_with(&['a', 'c'][..]));
+    /// ```
+    pub fn starts_with_zc(&self, prefix: impl AsRef<str>) -> bool {
+        self.as_str().starts_with(prefix.as_ref())
+    }
+
+    /// Returns `true` if this string ends with `suffix`.
+    ///
+    /// Accepts anything implementing `AsRef<str>` so a `ZCString` or
+    /// `String` can be passed without borrowing it as `&str` first.
+    ///
+    /// Named `ends_with_zc` rather than `ends_with` so the pattern based
+    /// `str::ends_with` stays reachable through `Deref`. That one can't take a
+    /// `&ZCString`, `str`'s `Pattern` trait is unstable, so
+    /// `zc.ends_with(&other)` doesn't compile, pass `other.as_str()` instead.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats and dogs");
+    /// let dogs = ZCString::from("dogs");
+    ///
+    /// assert!(zc.ends_with_zc(&dogs) && !zc.ends_with_zc("cats"));
+    /// assert!(zc.ends_with(dogs.as_str()));
+    ///
+    /// // str patterns through deref
+    /// assert!(zc.ends_with('s'));
+    /// assert!(zc.ends_with(char::is_alphabetic));
+    /// ```
+    pub fn ends_with_zc(&self, suffix: impl AsRef<str>) -> bool {
+        self.as_str().ends_with(suffix.as_ref())
+    }
+
+    /// Returns `true` if `needle` is found anywhere in this string.
+    ///
+    /// Accepts anything implementing `AsRef<str>` so a `ZCString` or
+    /// `String` can be passed without borrowing it as `&str` first.
+    ///
+    /// Named `contains_zc` rather than `contains` so the pattern based
+    /// `str::contains` stays reachable through `Deref`. That one can't take a
+    /// `&ZCString`, `str`'s `Pattern` trait is unstable, so
+    /// `zc.contains(&other)` doesn't compile, pass `other.as_str()` instead.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats and dogs");
+    /// let and = ZCString::from("and");
+    ///
+    /// assert!(zc.contains_zc(&and) && !zc.contains_zc("frogs"));
+    /// assert!(zc.contains(and.as_str()));
+    ///
+    /// // str patterns through deref
+    /// assert!(zc.contains(' ') && !zc.contains('x'));
+    /// assert!(zc.contains(char::is_whitespace));
+    /// ```
+    pub fn contains_zc(&self, needle: impl AsRef<str>) -> bool {
+        self.as_str().contains(needle.as_ref())
+    }
+
+    /// Returns `true` if this string equals `other`, ignoring ASCII case.
+    ///
+    /// Accepts anything implementing `AsRef<str>`. See [`CaseInsensitive`]
+    /// for using case-insensitive strings as map keys.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("Content-Type");
+    ///
+    /// assert!(zc.eq_ignore_ascii_case("content-type"));
+    /// assert!(zc.eq_ignore_ascii_case(ZCString::from("CONTENT-TYPE")));
+    /// assert!(!zc.eq_ignore_ascii_case("content-length"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: impl AsRef<str>) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_ref())
+    }
+
+    /// Returns this string with ASCII letters mapped to lowercase.
+    ///
+    /// If the string contains no ASCII uppercase letters a zero-copy clone is
+    /// returned, otherwise a new buffer is allocated. Non-ASCII characters
+    /// are left unchanged, see [`Self::to_lowercase_zc`] for full Unicode
+    /// case mapping. `str::to_ascii_lowercase`, returning a `String`, remains
+    /// available through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("route/users");
+    /// let lower = zc.to_ascii_lowercase_zc();
+    ///
+    /// // already lowercase, still points to zc
+    /// assert!(zc.source_of(&lower));
+    ///
+    /// let mixed = ZCString::from("Route/Users");
+    /// assert_eq!(mixed.to_ascii_lowercase_zc(), "route/users");
+    /// ```
+    pub fn to_ascii_lowercase_zc(&self) -> ZCString {
+        if self.bytes().any(|b| b.is_ascii_uppercase()) {
+            let result = ArcStr::init_with(self.len(), |buffer| {
+                buffer.copy_from_slice(self.as_bytes());
+                buffer.make_ascii_lowercase();
+            });
+            // ASCII case mapping never produces invalid UTF-8
+            ZCString::from(result.expect("ASCII case mapping preserves UTF-8"))
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns this string with ASCII letters mapped to uppercase.
+    ///
+    /// If the string contains no ASCII lowercase letters a zero-copy clone is
+    /// returned, otherwise a new buffer is allocated. Non-ASCII characters
+    /// are left unchanged, see [`Self::to_uppercase_zc`] for full Unicode
+    /// case mapping. `str::to_ascii_uppercase`, returning a `String`, remains
+    /// available through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("GET");
+    /// let upper = zc.to_ascii_uppercase_zc();
+    ///
+    /// // already uppercase, still points to zc
+    /// assert!(zc.source_of(&upper));
+    ///
+    /// let mixed = ZCString::from("Post");
+    /// assert_eq!(mixed.to_ascii_uppercase_zc(), "POST");
+    /// ```
+    pub fn to_ascii_uppercase_zc(&self) -> ZCString {
+        if self.bytes().any(|b| b.is_ascii_lowercase()) {
+            let result = ArcStr::init_with(self.len(), |buffer| {
+                buffer.copy_from_slice(self.as_bytes());
+                buffer.make_ascii_uppercase();
+            });
+            // ASCII case mapping never produces invalid UTF-8
+            ZCString::from(result.expect("ASCII case mapping preserves UTF-8"))
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns this string escaped for use inside a JSON string literal,
+    /// without the surrounding quotes.
+    ///
+    /// `"` and `\` are backslash escaped, as are the control characters
+    /// U+0000 to U+001F, using `\n`, `\r`, `\t`, `\b` and `\f` where
+    /// available and `\u00XX` otherwise. Everything else, including
+    /// non-ASCII characters, is kept as is. If nothing needs escaping a
+    /// zero-copy clone is returned.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let plain = ZCString::from("cats and dogs");
+    /// assert!(plain.same_buffer(&plain.json_escaped()));
+    ///
+    /// let zc = ZCString::from("say \"meow\"\n\\ \u{1}");
+    /// assert_eq!(zc.json_escaped(), r#"say \"meow\"\n\\ \u0001"#);
+    /// ```
+    pub fn json_escaped(&self) -> ZCString {
+        self.escaped(true, |c| match c {
+            '"' => Some("\\\""),
+            '\\' => Some("\\\\"),
+            '\n' => Some("\\n"),
+            '\r' => Some("\\r"),
+            '\t' => Some("\\t"),
+            '\u{8}' => Some("\\b"),
+            '\u{c}' => Some("\\f"),
+            _ => None,
+        })
+    }
+
+    /// Returns this string escaped for use in HTML text or attribute values.
+    ///
+    /// `&`, `<`, `>`, `"` and `'` are replaced by `&amp;`, `&lt;`, `&gt;`,
+    /// `&quot;` and `&#39;`, everything else is kept as is. If nothing needs
+    /// escaping a zero-copy clone is returned.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let plain = ZCString::from("cats and dogs");
+    /// assert!(plain.same_buffer(&plain.html_escaped()));
+    ///
+    /// let zc = ZCString::from("<b>cats & 'dogs'</b>");
+    /// assert_eq!(zc.html_escaped(), "&lt;b&gt;cats &amp; &#39;dogs&#39;&lt;/b&gt;");
+    /// ```
+    pub fn html_escaped(&self) -> ZCString {
+        self.escaped(false, |c| match c {
+            '&' => Some("&amp;"),
+            '<' => Some("&lt;"),
+            '>' => Some("&gt;"),
+            '"' => Some("&quot;"),
+            '\'' => Some("&#39;"),
+            _ => None,
+        })
+    }
+
+    // replaces chars mapped by escape, and with escape_controls any other
+    // control chars below U+0020 by \u00XX, cloning self if nothing is
+    // replaced
+    fn escaped<F>(&self, escape_controls: bool, escape: F) -> ZCString
+    where
+        F: Fn(char) -> Option<&'static str>,
+    {
+        let needs_escape = |c: char| escape(c).is_some() || (escape_controls && c < ' ');
+
+        let Some(first) = self.find(needs_escape) else {
+            return self.clone();
+        };
+
+        let mut builder = ZCStringBuilder::with_capacity(self.len() + 8);
+        builder.push_str(&self[..first]);
+
+        for c in self[first..].chars() {
+            match escape(c) {
+                Some(escaped) => builder.push_str(escaped),
+                None if escape_controls && c < ' ' => {
+                    builder.push_str(&format!("\\u{:04x}", c as u32));
+                }
+                None => builder.push(c),
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Creates a new `ZCString` by repeating this string `n` times.
+    ///
+    /// The result is built directly in a single `ArcStr` allocation, an empty
+    /// result does not allocate. `str::repeat`, returning a `String`,
+    /// remains available through `Deref`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length would overflow, like [`str::repeat`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("ab");
+    /// assert_eq!(zc.repeat_zc(3), "ababab");
+    /// assert_eq!(zc.repeat_zc(0), "");
+    ///
+    /// let s: String = zc.repeat(2);
+    /// assert_eq!(s, "abab");
+    /// ```
+    pub fn repeat_zc(&self, n: usize) -> ZCString {
+        let len = self.len().checked_mul(n).expect("capacity overflow");
+
+        if len == 0 {
+            return ZCString::new();
+        }
+
+        let result = ArcStr::init_with(len, |buffer| {
+            buffer
+                .chunks_exact_mut(self.len())
+                .for_each(|chunk| chunk.copy_from_slice(self.as_bytes()));
+        });
+
+        // repeating valid UTF-8 always yields valid UTF-8
+        ZCString::from(result.expect("repeat preserves UTF-8"))
+    }
+
+    /// Replaces all matches of `from` with `to`.
+    ///
+    /// If `from` does not occur a zero-copy clone of this string is returned,
+    /// a new buffer is only allocated when at least one replacement happens.
+    /// The pattern based `str::replace`, returning a `String`, remains
+    /// available through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats and cats");
+    /// assert_eq!(zc.replace_zc("cats", "dogs"), "dogs and dogs");
+    ///
+    /// // no match, still points to zc
+    /// let same = zc.replace_zc("frogs", "toads");
+    /// assert!(zc.source_of(&same));
+    ///
+    /// // str patterns through deref
+    /// let s: String = zc.replace('c', "b");
+    /// assert_eq!(s, "bats and bats");
+    /// ```
+    pub fn replace_zc(&self, from: &str, to: &str) -> ZCString {
+        if self.as_str().contains(from) {
+            ZCString::from_str_without_source(&self.as_str().replace(from, to))
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Replaces the first `count` matches of `from` with `to`.
+    ///
+    /// If `from` does not occur or `count` is zero a zero-copy clone of this
+    /// string is returned, a new buffer is only allocated when at least one
+    /// replacement happens. The pattern based `str::replacen` remains
+    /// available through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats and cats");
+    /// assert_eq!(zc.replacen_zc("cats", "dogs", 1), "dogs and cats");
+    ///
+    /// // nothing replaced, still points to zc
+    /// assert!(zc.source_of(&zc.replacen_zc("cats", "dogs", 0)));
+    /// assert!(zc.source_of(&zc.replacen_zc("frogs", "toads", 1)));
+    /// ```
+    pub fn replacen_zc(&self, from: &str, to: &str, count: usize) -> ZCString {
+        if count > 0 && self.as_str().contains(from) {
+            ZCString::from_str_without_source(&self.as_str().replacen(from, to, count))
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Releases the unused part of this string's buffer, when this string
+    /// views only part of a buffer it is the sole owner of, by copying it
+    /// into an exactly sized buffer of its own.
+    ///
+    /// This is a no-op when the buffer is shared with other strings, since
+    /// copying could not free it, and when the string already spans its
+    /// whole buffer. Useful for long-lived caches of small strings cut from
+    /// larger ones. Use [`Self::detach`] to copy unconditionally.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let mut cats = source.substr(0..4);
+    ///
+    /// // shared with source, nothing to gain
+    /// cats.shrink_to_fit();
+    /// assert!(cats.same_buffer(&source));
+    ///
+    /// drop(source);
+    /// cats.shrink_to_fit();
+    /// assert_eq!(cats, "cats");
+    /// assert_eq!(cats.parent().len(), 4);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let parent = self.0.parent();
+
+        if parent.len() > self.len() && ArcStr::strong_count(parent) == Some(1) {
+            *self = self.detach();
+        }
+    }
+
+    /// Edits this string through a `&mut str` into a fresh copy of it.
+    ///
+    /// `ArcStr` buffers are immutable once built, even when uniquely owned,
+    /// so a `&mut str` into the current buffer can't be handed out. Instead
+    /// the contents are copied into a single new buffer and `f` edits that
+    /// buffer before it is sealed, no intermediate `String` is needed. Unlike
+    /// `Arc::make_mut` this always copies, even when the buffer is uniquely
+    /// owned.
+    ///
+    /// Afterwards this string is detached from its previous buffer and from
+    /// any source it was borrowed from.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("Cats and dogs");
+    /// let mut word = source.substr(0..4);
+    ///
+    /// word.make_mut(|s| s.make_ascii_uppercase());
+    ///
+    /// assert_eq!(word, "CATS");
+    /// assert!(!source.source_of(&word));
+    /// assert_eq!(source, "Cats and dogs");
+    ///
+    /// let mut empty = ZCString::from("");
+    /// empty.make_mut(|s| s.make_ascii_uppercase());
+    /// assert_eq!(empty, "");
+    /// ```
+    pub fn make_mut<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut str) -> R,
+    {
+        if self.is_empty() {
+            // ArcStr::init_with can't create an empty string
+            *self = ZCString::new();
+            return f(<&mut str>::default());
+        }
+
+        let mut output = None;
+
+        let result = ArcStr::init_with(self.len(), |buffer| {
+            buffer.copy_from_slice(self.as_bytes());
+            // a copy of a str is valid UTF-8
+            if let Ok(s) = core::str::from_utf8_mut(buffer) {
+                output = Some(f(s));
+            }
+        });
+
+        // &mut str can't be used to produce invalid UTF-8 safely
+        *self = ZCString::from(result.expect("make_mut preserves UTF-8"));
+        output.expect("make_mut buffer is valid UTF-8")
+    }
+
+    /// Appends `s` to the end of this string.
+    ///
+    /// `ArcStr` buffers can't grow in place, so this copies both parts into
+    /// a single new buffer. Afterwards this string is detached from its
+    /// previous buffer and from any source it was borrowed from. Every push
+    /// reallocates, use [`ZCStringBuilder`] when appending in a loop.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    /// let mut s = source.substr(0..4);
+    ///
+    /// s.push_str(" and frogs");
+    /// s.push('!');
+    ///
+    /// assert_eq!(s, "cats and frogs!");
+    /// assert!(!source.source_of(&s));
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        let result = ArcStr::init_with(len + s.len(), |buffer| {
+            buffer[..len].copy_from_slice(self.as_bytes());
+            buffer[len..].copy_from_slice(s.as_bytes());
+        });
+
+        // concatenating valid UTF-8 always yields valid UTF-8
+        *self = ZCString::from(result.expect("push_str preserves UTF-8"));
+    }
+
+    /// Appends the character `c` to the end of this string, see
+    /// [`Self::push_str`].
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0u8; 4]));
+    }
+
+    // appends whatever f pushes, leaving self untouched if nothing was
+    fn extend_with<F: FnOnce(&mut ZCStringBuilder)>(&mut self, f: F) {
+        let mut builder = ZCStringBuilder::with_capacity(self.len());
+        builder.push_str(self);
+        f(&mut builder);
+
+        if builder.len() != self.len() {
+            *self = builder.build();
+        }
+    }
+
+    /// Returns the Unicode lowercase equivalent of this string.
+    ///
+    /// Unlike [`Self::to_ascii_lowercase_zc`] this always allocates a new
+    /// buffer. `str::to_lowercase`, returning a `String`, remains available
+    /// through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("ΌΣΟΣ");
+    /// assert_eq!(zc.to_lowercase_zc(), "όσος");
+    /// ```
+    pub fn to_lowercase_zc(&self) -> ZCString {
+        ZCString::from_str_without_source(&self.as_str().to_lowercase())
+    }
+
+    /// Returns the Unicode uppercase equivalent of this string.
+    ///
+    /// Unlike [`Self::to_ascii_uppercase_zc`] this always allocates a new
+    /// buffer. `str::to_uppercase`, returning a `String`, remains available
+    /// through `Deref`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("tschüß");
+    /// assert_eq!(zc.to_uppercase_zc(), "TSCHÜSS");
+    /// ```
+    pub fn to_uppercase_zc(&self) -> ZCString {
+        ZCString::from_str_without_source(&self.as_str().to_uppercase())
+    }
+
+    /// Transforms the current [`ZCString`] into a new view using a closure,
+    /// provided the result is a sub-slice of the original.
+    ///
+    /// This is a high-level utility for performing zero-copy operations like
+    /// trimming or pattern-based slicing using standard [`str`] methods.
+    ///
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("  zero-copy  ");
+    ///
+    /// // Use map to trim the string without new allocations
+    /// let trimmed = zc.map(|s| s.trim());
+    ///
+    /// assert_eq!(trimmed, "zero-copy");
+    /// ```
+    pub fn map<F>(&self, f: F) -> ZCString
+    where
+        F: FnOnce(&str) -> &str,
+    {
+        self.from_substr(f(self))
+    }
+
+    /// Like [`Self::map`] but for transforms that may allocate: a borrowed
+    /// result is turned into a zero-copy view as by [`Self::from_substr`],
+    /// an owned result into a new allocation.
+    ///
+    /// ### Example
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("  cats  ");
+    ///
+    /// let trimmed = zc.map_cow(|s| Cow::Borrowed(s.trim()));
+    /// assert_eq!(trimmed, "cats");
+    /// assert!(zc.source_of(&trimmed));
+    ///
+    /// let upper = zc.map_cow(|s| Cow::Owned(s.trim().to_uppercase()));
+    /// assert_eq!(upper, "CATS");
+    /// assert!(!zc.source_of(&upper));
+    ///
+    /// // e.g. String::from_utf8_lossy style helpers returning Cow
+    /// let plain = zc.map_cow(|s| String::from_utf8_lossy(s.as_bytes()));
+    /// assert!(plain.same_buffer(&zc));
+    /// ```
+    pub fn map_cow<F>(&self, f: F) -> ZCString
+    where
+        F: FnOnce(&str) -> alloc::borrow::Cow<'_, str>,
+    {
+        match f(self) {
+            alloc::borrow::Cow::Borrowed(s) => self.from_substr(s),
+            alloc::borrow::Cow::Owned(s) => ZCString::from_str_without_source(&s),
+        }
+    }
+
+    /// Wraps a standard string iterator to produce [`ZCString`] items instead of `&str`.
+    ///
+    /// This method allows you to leverage existing [`str`] iteration logic (like `.lines()` or `.split()`)
+    /// while automatically promoting each yielded slice into a zero-copy [`ZCString`].
+    ///
+    /// The resulting items share the same underlying [`arcstr::ArcStr`] as this source,
+    /// ensuring memory stays alive as long as any yielded item exists.
+    ///
+    /// ### Arguments
+    /// * `f` - A closure that takes a reference to the inner string and returns an iterator yielding `&str`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("line1\nline2\nline3");
+    ///
+    /// // Wrap the standard .lines() iterator
+    /// let mut iter = zc.wrap_iter(|s| s.lines());
+    ///
+    /// assert_eq!(iter.next().unwrap(), "line1");
+    /// assert_eq!(iter.next().unwrap(), "line2");
+    /// ```
+    pub fn wrap_iter<'a, F, I>(&'a self, f: F) -> ZCStringIterWrapper<'a, I>
+    where
+        F: FnOnce(&'a str) -> I,
+        I: Iterator<Item = &'a str>,
+    {
+        ZCStringIterWrapper {
+            source: self.clone(),
+            inner: f(self.as_str()),
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the first match of `pat` as a zero-copy [`ZCString`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("level=error msg=timeout");
+    /// let found = zc.find_substr("error").unwrap();
+    ///
+    /// assert_eq!(found, "error");
+    /// assert!(zc.source_of(&found));
+    /// assert_eq!(zc.find_substr("warning"), None);
+    /// ```
+    pub fn find_substr(&self, pat: &str) -> Option<ZCString> {
+        self.find(pat)
+            .map(|start| self.substr(start..start + pat.len()))
+    }
+
+    /// Returns the part of this string after the first match of `pat` as a
+    /// zero-copy [`ZCString`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("key=value=more");
+    /// let value = zc.after("=").unwrap();
+    ///
+    /// assert_eq!(value, "value=more");
+    /// assert!(zc.source_of(&value));
+    /// assert_eq!(zc.after(":"), None);
+    /// ```
+    pub fn after(&self, pat: &str) -> Option<ZCString> {
+        self.find(pat).map(|start| self.substr(start + pat.len()..))
+    }
+
+    /// Returns the part of this string before the first match of `pat` as a
+    /// zero-copy [`ZCString`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("key=value=more");
+    /// let key = zc.before("=").unwrap();
+    ///
+    /// assert_eq!(key, "key");
+    /// assert!(zc.source_of(&key));
+    /// assert_eq!(zc.before(":"), None);
+    /// ```
+    pub fn before(&self, pat: &str) -> Option<ZCString> {
+        self.find(pat).map(|start| self.substr(..start))
+    }
+
+    /// Parses this string as `T`, the same as `str::parse` but spelled out
+    /// for use on extracted fields. No copy is made, the number is parsed
+    /// straight from the shared buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("status=404 took=1.5");
+    ///
+    /// assert_eq!(zc.after("status=").unwrap().before(" ").unwrap().parse_to::<u16>(), Ok(404));
+    /// assert_eq!(zc.after("took=").unwrap().parse_to::<f64>(), Ok(1.5));
+    /// assert!(zc.parse_to::<u16>().is_err());
+    /// ```
+    pub fn parse_to<T: core::str::FromStr>(&self) -> Result<T, T::Err> {
+        self.as_str().parse()
+    }
+
+    /// Same as [`Self::parse_to`] but ignores leading and trailing
+    /// whitespace, as commonly found in padded CSV or log fields.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from(" 42\t");
+    ///
+    /// assert_eq!(zc.parse_trimmed::<u64>(), Ok(42));
+    /// assert!(zc.parse_to::<u64>().is_err());
+    /// ```
+    pub fn parse_trimmed<T: core::str::FromStr>(&self) -> Result<T, T::Err> {
+        self.trim().parse()
+    }
+
+    /// Parses a number at the start of this string, returning it along with
+    /// the rest of the string as a zero-copy [`ZCString`].
+    ///
+    /// The longest leading run that looks like a decimal number (optional
+    /// sign, digits, fraction and exponent) and parses as `T` is taken, so an
+    /// integer type stops at a `.` while a float type continues past it.
+    /// Returns `None` if no prefix parses, leading whitespace isn't skipped.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("12.5ms");
+    ///
+    /// let (value, rest) = zc.parse_prefix::<f64>().unwrap();
+    /// assert_eq!((value, rest.as_str()), (12.5, "ms"));
+    /// assert!(zc.source_of(&rest));
+    ///
+    /// let (value, rest) = zc.parse_prefix::<u32>().unwrap();
+    /// assert_eq!((value, rest.as_str()), (12, ".5ms"));
+    ///
+    /// let (value, rest) = ZCString::from("-3e2,").parse_prefix::<f32>().unwrap();
+    /// assert_eq!((value, rest.as_str()), (-300.0, ","));
+    ///
+    /// assert_eq!(ZCString::from("-7").parse_prefix::<u8>(), None);
+    /// assert_eq!(ZCString::from("ms").parse_prefix::<u8>(), None);
+    /// ```
+    pub fn parse_prefix<T: core::str::FromStr>(&self) -> Option<(T, ZCString)> {
+        (1..=number_prefix_len(self.as_bytes()))
+            .rev()
+            .find_map(|end| Some((self[..end].parse().ok()?, self.substr(end..))))
+    }
+
+    /// Splits this string on the last occurrence of `pat`, returning the
+    /// parts before and after it as zero-copy [`ZCString`]s. Same as
+    /// [`str::rsplit_once`] with a `char` pattern, which remains available
+    /// through `Deref` for other patterns.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("archive.tar.gz");
+    /// let (stem, ext) = zc.rsplit_once_zc('.').unwrap();
+    ///
+    /// assert_eq!((stem.as_str(), ext.as_str()), ("archive.tar", "gz"));
+    /// assert!(zc.source_of(&stem) && zc.source_of(&ext));
+    ///
+    /// assert_eq!(zc.rsplit_once_zc('/'), None);
+    ///
+    /// // str patterns through deref
+    /// assert_eq!(zc.rsplit_once(".t"), Some(("archive", "ar.gz")));
+    /// ```
+    pub fn rsplit_once_zc(&self, pat: char) -> Option<(ZCString, ZCString)> {
+        let start = self.rfind(pat)?;
+        Some((self.substr(..start), self.substr(start + pat.len_utf8()..)))
+    }
+
+    /// Returns an iterator over at most `n` pieces of this string separated
+    /// by `pat`, starting from the end, as zero-copy [`ZCString`]s. The last
+    /// piece holds whatever remains of the start of the string. Same as
+    /// [`str::rsplitn`] with a `char` pattern, which remains available
+    /// through `Deref` for other patterns.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("/usr/local/bin/cat");
+    /// let parts: Vec<ZCString> = zc.rsplitn_zc(2, '/').collect();
+    ///
+    /// assert_eq!(parts, ["cat", "/usr/local/bin"]);
+    /// assert!(parts.iter().all(|p| zc.source_of(p)));
+    ///
+    /// let parts: Vec<ZCString> = zc.rsplitn_zc(10, '/').collect();
+    /// assert_eq!(parts, ["cat", "bin", "local", "usr", ""]);
+    /// assert_eq!(zc.rsplitn_zc(0, '/').count(), 0);
+    /// ```
+    pub fn rsplitn_zc(
+        &self,
+        n: usize,
+        pat: char,
+    ) -> ZCStringIterWrapper<'_, core::str::RSplitN<'_, char>> {
+        self.wrap_iter(|s| s.rsplitn(n, pat))
+    }
+
+    /// Returns the single character starting at byte offset `byte_idx` as a
+    /// zero-copy [`ZCString`].
+    ///
+    /// Returns `None` if `byte_idx` is out of range or not on a char
+    /// boundary. Plain `chars()` and `char_indices()` are available through
+    /// deref, combine them with `char_at` to recover a slice of the source.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("añb");
+    ///
+    /// let n = zc.char_at(1).unwrap();
+    /// assert_eq!(n, "ñ");
+    /// assert!(zc.source_of(&n));
+    ///
+    /// // inside of the two byte 'ñ'
+    /// assert_eq!(zc.char_at(2), None);
+    /// assert_eq!(zc.char_at(4), None);
+    ///
+    /// let letters: Vec<ZCString> = zc
+    ///     .char_indices()
+    ///     .filter(|(_, c)| c.is_alphabetic())
+    ///     .filter_map(|(i, _)| zc.char_at(i))
+    ///     .collect();
+    /// assert_eq!(letters, ["a", "ñ", "b"]);
+    /// ```
+    pub fn char_at(&self, byte_idx: usize) -> Option<ZCString> {
+        if !self.is_char_boundary(byte_idx) {
+            return None;
+        }
+
+        let c = self[byte_idx..].chars().next()?;
+        Some(self.substr(byte_idx..byte_idx + c.len_utf8()))
+    }
+
+    /// Returns an iterator over the lines of this string as zero-copy
+    /// [`ZCString`]s. Shorthand for `wrap_iter(|s| s.lines())`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats\ndogs\r\nfrogs");
+    /// let lines: Vec<ZCString> = zc.lines_zc().collect();
+    ///
+    /// assert_eq!(lines, ["cats", "dogs", "frogs"]);
+    /// assert!(lines.iter().all(|l| zc.source_of(l)));
+    /// ```
+    pub fn lines_zc(&self) -> ZCStringIterWrapper<'_, core::str::Lines<'_>> {
+        self.wrap_iter(|s| s.lines())
+    }
+
+    /// Returns an iterator over the lines of this string as zero-copy
+    /// [`ZCString`]s, each including its `\n` or `\r\n` terminator. The last
+    /// line is yielded as-is whether or not it is terminated, so
+    /// concatenating the lines reproduces the original string exactly.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cats\ndogs\r\n\nfrogs");
+    /// let lines: Vec<ZCString> = zc.lines_with_terminator().collect();
+    ///
+    /// assert_eq!(lines, ["cats\n", "dogs\r\n", "\n", "frogs"]);
+    /// assert!(lines.iter().all(|line| zc.source_of(line)));
+    /// assert_eq!(lines.concat(), zc.as_str());
+    /// ```
+    pub fn lines_with_terminator(
+        &self,
+    ) -> ZCStringIterWrapper<'_, core::str::SplitInclusive<'_, char>> {
+        self.wrap_iter(|s| s.split_inclusive('\n'))
+    }
+
+    #[cfg(feature = "graphemes")]
+    /// Returns an iterator over the extended grapheme clusters of this string
+    /// as zero-copy [`ZCString`]s. Shorthand for
+    /// `wrap_iter(|s| s.graphemes(true))` using `unicode-segmentation`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// // "e" + combining acute accent, and a family emoji ZWJ sequence
+    /// let zc = ZCString::from("cafe\u{301} 👨\u{200d}👩\u{200d}👧!");
+    /// let graphemes: Vec<ZCString> = zc.graphemes_zc().collect();
+    ///
+    /// assert_eq!(graphemes.len(), 7);
+    /// assert_eq!(graphemes[3], "e\u{301}");
+    /// assert_eq!(graphemes[5], "👨\u{200d}👩\u{200d}👧");
+    /// assert!(graphemes.iter().all(|g| zc.source_of(g)));
+    /// ```
+    pub fn graphemes_zc(&self) -> ZCStringIterWrapper<'_, unicode_segmentation::Graphemes<'_>> {
+        use unicode_segmentation::UnicodeSegmentation;
+        self.wrap_iter(|s| s.graphemes(true))
+    }
+
+    /// Returns an iterator over the lines of this string with their common
+    /// leading indentation removed, each a zero-copy [`ZCString`]. Lines are
+    /// split as by [`str::lines`].
+    ///
+    /// The indentation is the smallest number of leading spaces and tabs
+    /// over all lines that are not blank, spaces and tabs count alike.
+    /// Blank lines don't affect it and come out empty if shorter.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("    cats\n      and\n\n    dogs\n");
+    /// let lines: Vec<ZCString> = zc.lines_dedented().collect();
+    ///
+    /// assert_eq!(lines, ["cats", "  and", "", "dogs"]);
+    /// assert!(lines.iter().all(|line| zc.source_of(line)));
+    /// ```
+    pub fn lines_dedented(&self) -> impl Iterator<Item = ZCString> + '_ {
+        let indent = self.common_indent();
+        self.wrap_iter(move |s| s.lines().map(move |line| strip_indent(line, indent)))
+    }
+
+    /// Returns this string with the common leading indentation of its lines
+    /// removed, see [`Self::lines_dedented`]. Line terminators are kept as
+    /// they are. If there is no common indentation a zero-copy clone is
+    /// returned, otherwise a new buffer is allocated.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("    cats\n      and\r\n\n    dogs\n");
+    /// assert_eq!(zc.dedent(), "cats\n  and\r\n\ndogs\n");
+    ///
+    /// let flat = ZCString::from("cats\n  dogs");
+    /// assert!(flat.same_buffer(&flat.dedent()));
+    /// ```
+    pub fn dedent(&self) -> ZCString {
+        let indent = self.common_indent();
+        if indent == 0 {
+            return self.clone();
+        }
+
+        let mut builder = ZCStringBuilder::with_capacity(self.len());
+        for line in self.split_inclusive('\n') {
+            builder.push_str(strip_indent(line, indent));
+        }
+        builder.build()
+    }
+
+    // smallest leading whitespace of the non-blank lines
+    fn common_indent(&self) -> usize {
+        self.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(leading_indent)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Returns an iterator over the whitespace separated words of this string
+    /// as zero-copy [`ZCString`]s. Shorthand for
+    /// `wrap_iter(|s| s.split_whitespace())`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("  cats and\tdogs ");
+    /// let words: Vec<ZCString> = zc.split_whitespace_zc().collect();
+    ///
+    /// assert_eq!(words, ["cats", "and", "dogs"]);
+    /// ```
+    pub fn split_whitespace_zc(&self) -> ZCStringIterWrapper<'_, core::str::SplitWhitespace<'_>> {
+        self.wrap_iter(|s| s.split_whitespace())
+    }
+
+    /// Returns an iterator over the pieces of this string separated by `pat`
+    /// as zero-copy [`ZCString`]s, skipping a trailing empty piece.
+    /// Shorthand for `wrap_iter(|s| s.split_terminator(pat))`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("a;b;c;");
+    /// let parts: Vec<ZCString> = zc.split_terminator_zc(";").collect();
+    ///
+    /// assert_eq!(parts, ["a", "b", "c"]);
+    /// assert!(parts.iter().all(|p| zc.source_of(p)));
+    ///
+    /// // only the trailing empty piece is skipped
+    /// let parts: Vec<ZCString> = ZCString::from(";a;;b").split_terminator_zc(";").collect();
+    /// assert_eq!(parts, ["", "a", "", "b"]);
+    /// ```
+    pub fn split_terminator_zc<'a>(
+        &'a self,
+        pat: &'a str,
+    ) -> ZCStringIterWrapper<'a, core::str::SplitTerminator<'a, &'a str>> {
+        self.wrap_iter(|s| s.split_terminator(pat))
+    }
+
+    /// Returns an iterator over the pieces of this string separated by `pat`
+    /// as zero-copy [`ZCString`]s, each piece keeping the `pat` that ends it.
+    /// Like [`str::split_inclusive`] there is no trailing empty piece.
+    /// Shorthand for `wrap_iter(|s| s.split_inclusive(pat))`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("a;b;;c");
+    /// let parts: Vec<ZCString> = zc.split_inclusive_zc(';').collect();
+    ///
+    /// assert_eq!(parts, ["a;", "b;", ";", "c"]);
+    /// assert!(parts.iter().all(|p| zc.source_of(p)));
+    ///
+    /// let parts: Vec<ZCString> = ZCString::from("a;b;").split_inclusive_zc(';').collect();
+    /// assert_eq!(parts, ["a;", "b;"]);
+    /// ```
+    pub fn split_inclusive_zc(
+        &self,
+        pat: char,
+    ) -> ZCStringIterWrapper<'_, core::str::SplitInclusive<'_, char>> {
+        self.wrap_iter(|s| s.split_inclusive(pat))
+    }
+
+    /// Returns an iterator over every run of `n` consecutive characters as
+    /// overlapping zero-copy [`ZCString`]s, advancing one character at a
+    /// time. Windows respect char boundaries, so their byte lengths vary.
+    /// Yields nothing if the string has fewer than `n` characters or `n` is 0.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("cañon");
+    /// let windows: Vec<ZCString> = zc.char_windows(3).collect();
+    ///
+    /// assert_eq!(windows, ["cañ", "año", "ñon"]);
+    /// assert!(windows.iter().all(|w| zc.source_of(w)));
+    /// assert_eq!(zc.char_windows(5).count(), 1);
+    /// assert_eq!(zc.char_windows(6).count(), 0);
+    /// assert_eq!(zc.char_windows(0).count(), 0);
+    /// ```
+    pub fn char_windows(&self, n: usize) -> CharWindows {
+        // end of the n-th char
+        let end = n
+            .checked_sub(1)
+            .and_then(|last| self.char_indices().map(|(i, c)| i + c.len_utf8()).nth(last));
+
+        CharWindows {
+            remaining: self.clone(),
+            end,
+        }
+    }
+
+    /// Returns an iterator over consecutive zero-copy chunks of at most
+    /// `max_bytes` bytes each.
+    ///
+    /// Chunks always end on a char boundary, so a chunk may be shorter than
+    /// `max_bytes` to avoid splitting a multi-byte character. A chunk holds at
+    /// least one character, if `max_bytes` is smaller than a character that
+    /// character is yielded on its own. `max_bytes == 0` yields nothing.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let zc = ZCString::from("añbñc");
+    /// let chunks: Vec<ZCString> = zc.byte_chunks(3).collect();
+    ///
+    /// assert_eq!(chunks, ["añ", "bñ", "c"]);
+    /// assert!(chunks.iter().all(|c| zc.source_of(c)));
+    /// assert_eq!(zc.byte_chunks(0).count(), 0);
+    /// ```
+    pub fn byte_chunks(&self, max_bytes: usize) -> ByteChunks {
+        ByteChunks {
+            remaining: if max_bytes == 0 {
+                ZCString::new()
+            } else {
+                self.clone()
+            },
+            max_bytes,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Create a ZCString by reading a range of bytes from a
+    /// an object supporting Read and Seek traits. The range must
+    /// contain valid UTF-8
+    ///
+    /// Bounds are absolute offsets from the start of the input. An unbounded
+    /// start is the current position and an unbounded end is the end of the
+    /// input as of the call, so `..` reads the rest of the input from the
+    /// current position. On success the position is left after the range,
+    /// range errors leave it unchanged.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::{Cursor, Seek, SeekFrom};
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // test data in a form that supports Read & Seek traits
+    /// // as if coming from a File
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    /// // read "and" from 'data'
+    /// let mut r = ZCString::read_range(&mut data, 5..8)?;
+    /// assert_eq!(r, "and");
+    ///
+    /// // unbounded bounds relative to a non-zero position
+    /// data.seek(SeekFrom::Start(2))?;
+    /// assert_eq!(ZCString::read_range(&mut data, ..)?, "ts and dogs");
+    ///
+    /// data.seek(SeekFrom::Start(2))?;
+    /// assert_eq!(ZCString::read_range(&mut data, 5..)?, "and dogs");
+    ///
+    /// data.seek(SeekFrom::Start(2))?;
+    /// assert_eq!(ZCString::read_range(&mut data, ..8)?, "ts and");
+    /// assert_eq!(data.position(), 8);
+    ///
+    /// // the start lies past the end, the position is left as is
+    /// assert!(ZCString::read_range(&mut data, ..4).is_err());
+    /// assert_eq!(data.position(), 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// An empty bounded range returns an empty `ZCString` without any
+    /// seeking or reading.
+    ///
+    /// ```
+    /// # use std::io::{Read, Seek, SeekFrom};
+    /// # use zcstring::ZCString;
+    /// struct NoIo;
+    ///
+    /// impl Read for NoIo {
+    ///     fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+    ///         panic!("read")
+    ///     }
+    /// }
+    ///
+    /// impl Seek for NoIo {
+    ///     fn seek(&mut self, _: SeekFrom) -> std::io::Result<u64> {
+    ///         panic!("seek")
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(ZCString::read_range(&mut NoIo, 5..5).unwrap(), "");
+    /// assert_eq!(ZCString::read_range(&mut NoIo, 5..=4).unwrap(), "");
+    /// ```
+    pub fn read_range<I, R>(input: &mut I, range: R) -> Result<ZCString, ReaderError>
+    where
+        I: Read + Seek,
+        R: RangeBounds<u64>,
+    {
+        Self::read_range_limited(input, range, u64::MAX)
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::read_range`] but fails with [`ReaderError::TooLarge`]
+    /// if the range is longer than `max_bytes`. The check happens before
+    /// any buffer is allocated, making this suitable for untrusted inputs.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zcstring::{ReaderError, ZCString};
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    ///
+    /// let r = ZCString::read_range_limited(&mut data, 0..4, 4).unwrap();
+    /// assert_eq!(r, "Cats");
+    ///
+    /// // unbounded ranges are measured against the end of the input
+    /// let r = ZCString::read_range_limited(&mut data, 0.., 4);
+    /// assert!(matches!(r, Err(ReaderError::TooLarge { size: 13, limit: 4 })));
+    /// ```
+    pub fn read_range_limited<I, R>(
+        input: &mut I,
+        range: R,
+        max_bytes: u64,
+    ) -> Result<ZCString, ReaderError>
+    where
+        I: Read + Seek,
+        R: RangeBounds<u64>,
+    {
+        // resolve both bounds against a single snapshot of the position and
+        // the end, leaving the position untouched until the read itself.
+        // Bounded ranges don't touch the input at all until then.
+        let start_pos = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => *s + 1,
+            Bound::Unbounded => input.stream_position()?,
+        };
+
+        let end_pos = match range.end_bound() {
+            Bound::Included(e) => *e + 1,
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => {
+                let origin = match range.start_bound() {
+                    Bound::Unbounded => start_pos,
+                    _ => input.stream_position()?,
+                };
+
+                let end = input.seek(SeekFrom::End(0))?;
+                input.seek(SeekFrom::Start(origin))?;
+                end
+            }
+        };
+
+        if start_pos > end_pos {
+            // error
+            return Err(ReaderError::InvalidRange {
+                start: start_pos,
+                end: end_pos,
+            });
+        }
+
+        if end_pos - start_pos > max_bytes {
+            return Err(ReaderError::TooLarge {
+                size: end_pos - start_pos,
+                limit: max_bytes,
+            });
+        }
+
+        if start_pos == end_pos {
+            // edge case, no IO or allocation needed
+            return Ok(ZCString::new());
+        }
+
+        let mut io_error = Ok(());
+
+        let result = ArcStr::init_with((end_pos - start_pos) as usize, |buffer| {
+            io_error = (|| -> Result<(), std::io::Error> {
+                input.seek(SeekFrom::Start(start_pos))?;
+                input.read_exact(buffer)?;
+                Ok(())
+            })()
+        });
+
+        // a short read leaves the buffer partly filled, report the read
+        // failure rather than whatever UTF-8 error that causes
+        io_error?;
+        Ok(ZCString::from(result?))
+    }
+
+    #[cfg(feature = "std")]
+    /// Create a ZCString by reading bytes from an object supporting the Read trait.
+    /// The bytes must be valid UTF-8
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // test data in a form that supports Read & Seek traits
+    /// // as if coming from a File
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    /// // read "and" from 'data'
+    /// let mut r = ZCString::read(&mut data, 4)?;
+    /// assert_eq!(r, "Cats");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read<I: Read>(input: &mut I, bytes: usize) -> Result<ZCString, ReaderError> {
+        let mut io_error = Ok(());
+
+        let result = ArcStr::init_with(bytes, |buffer| {
+            io_error = (|| -> Result<(), std::io::Error> {
+                input.read_exact(buffer)?;
+                Ok(())
+            })()
+        })?;
+
+        match io_error {
+            Ok(()) => Ok(ZCString::from(result)),
+            Err(e) => Err(e)?,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::read`] but skips a leading UTF-8 byte order mark, the
+    /// result is a sub-slice starting after it. Input starting with a UTF-16
+    /// byte order mark fails with [`ReaderError::Utf16Bom`].
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::{ReaderError, ZCString};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut data: &[u8] = b"\xEF\xBB\xBFCats";
+    /// assert_eq!(ZCString::read_skip_bom(&mut data, 7)?, "Cats");
+    ///
+    /// // without a byte order mark this is the same as read
+    /// let mut data: &[u8] = b"Cats";
+    /// assert_eq!(ZCString::read_skip_bom(&mut data, 4)?, "Cats");
+    /// assert_eq!(ZCString::read_skip_bom(&mut data, 0)?, "");
+    ///
+    /// let mut data: &[u8] = b"\xFF\xFEC\0a\0t\0s\0";
+    /// assert!(matches!(
+    ///     ZCString::read_skip_bom(&mut data, 10),
+    ///     Err(ReaderError::Utf16Bom)
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_skip_bom<I: Read>(input: &mut I, bytes: usize) -> Result<ZCString, ReaderError> {
+        if bytes == 0 {
+            // ArcStr::init_with can't create an empty string
+            return Ok(ZCString::new());
+        }
+
+        let mut io_error = Ok(());
+        let mut utf16 = false;
+
+        let result = ArcStr::init_with(bytes, |buffer| {
+            io_error = input.read_exact(buffer);
+            utf16 = buffer.starts_with(&[0xff, 0xfe]) || buffer.starts_with(&[0xfe, 0xff]);
+        });
+
+        io_error?;
+
+        if utf16 {
+            return Err(ReaderError::Utf16Bom);
+        }
+
+        let result = ZCString::from(result?);
+        match result.strip_prefix('\u{feff}') {
+            Some(rest) => Ok(result.from_substr(rest)),
+            None => Ok(result),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Create a ZCString by reading everything remaining in an object
+    /// supporting the Read trait. The bytes must be valid UTF-8
+    ///
+    /// The length is not known up front, so the bytes are read into a
+    /// growable buffer and then copied once into a single `ArcStr`. Prefer
+    /// [`Self::read_range`] when the input also supports Seek.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut data: &[u8] = b"Cats and dogs";
+    /// let r = ZCString::read_all(&mut data)?;
+    /// assert_eq!(r, "Cats and dogs");
+    ///
+    /// // nothing left to read
+    /// assert_eq!(ZCString::read_all(&mut data)?, "");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_all<I: Read>(input: &mut I) -> Result<ZCString, ReaderError> {
+        Self::read_all_limited(input, u64::MAX)
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::read_all`] but fails with [`ReaderError::TooLarge`]
+    /// once more than `max_bytes` have been read, so an endless or untrusted
+    /// input can't exhaust memory. At most `max_bytes + 1` bytes are read,
+    /// the reported size is the number of bytes read before giving up.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zcstring::{ReaderError, ZCString};
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    /// let r = ZCString::read_all_limited(&mut data, 13).unwrap();
+    /// assert_eq!(r, "Cats and dogs");
+    ///
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    /// let r = ZCString::read_all_limited(&mut data, 4);
+    /// assert!(matches!(r, Err(ReaderError::TooLarge { size: 5, limit: 4 })));
+    ///
+    /// // never reads past the limit
+    /// let r = ZCString::read_all_limited(&mut std::io::repeat(b'a'), 1024);
+    /// assert!(matches!(r, Err(ReaderError::TooLarge { size: 1025, limit: 1024 })));
+    /// ```
+    pub fn read_all_limited<I: Read>(
+        input: &mut I,
+        max_bytes: u64,
+    ) -> Result<ZCString, ReaderError> {
+        let mut buffer = Vec::new();
+        input
+            .take(max_bytes.saturating_add(1))
+            .read_to_end(&mut buffer)?;
+
+        if buffer.len() as u64 > max_bytes {
+            return Err(ReaderError::TooLarge {
+                size: buffer.len() as u64,
+                limit: max_bytes,
+            });
+        }
+
+        if buffer.is_empty() {
+            // ArcStr::init_with can't create an empty string
+            return Ok(ZCString::new());
+        }
+
+        let result = ArcStr::init_with(buffer.len(), |dest| dest.copy_from_slice(&buffer))?;
+        Ok(ZCString::from(result))
+    }
+
+    /// Appends this string to `out`, for rendering many strings into one
+    /// reused buffer without the allocation of `to_string()` each time.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let fields = [ZCString::from("cats"), ZCString::from("and"), ZCString::from("dogs")];
+    /// let mut out = String::new();
+    ///
+    /// for field in &fields {
+    ///     field.append_to(&mut out);
+    ///     out.push(';');
+    /// }
+    ///
+    /// assert_eq!(out, "cats;and;dogs;");
+    /// ```
+    pub fn append_to(&self, out: &mut String) {
+        out.push_str(self.as_str());
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes the UTF-8 bytes of this string to `w`, without any
+    /// intermediate buffer.
+    ///
+    /// Structs holding `ZCString` fields can be written the same way with
+    /// `serde_json::to_writer`, each field is serialized as a plain string.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut out = Vec::new();
+    /// ZCString::from("cats ").write_to(&mut out)?;
+    /// ZCString::from("and dogs").write_to(&mut out)?;
+    ///
+    /// assert_eq!(out, b"cats and dogs");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(self.as_bytes())
+    }
+
+    #[cfg(feature = "std")]
+    /// Returns an iterator over the lines of `reader` as [`ZCString`]s,
+    /// without reading the whole input into memory.
+    ///
+    /// Each refill of the reader's buffer is copied into one `ArcStr` and
+    /// the complete lines within it are yielded as zero-copy sub-slices
+    /// sharing that allocation, so the chunk size follows the reader's
+    /// buffer capacity (see `BufReader::with_capacity`). A line straddling
+    /// two refills is copied once. Lines are split as by [`str::lines`].
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::BufReader;
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data: &[u8] = b"cats\ndogs\r\nfrogs";
+    /// let lines = ZCString::lines_from_reader(BufReader::new(data))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// assert_eq!(lines, ["cats", "dogs", "frogs"]);
+    ///
+    /// // "cats" and "dogs" arrived in the same chunk
+    /// assert!(arcstr::ArcStr::ptr_eq(lines[0].parent(), lines[1].parent()));
+    ///
+    /// // a final "\r" without "\n" is kept
+    /// let data: &[u8] = b"a\nb\r";
+    /// let lines = ZCString::lines_from_reader(BufReader::new(data))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(lines, ["a", "b\r"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines_from_reader<R: BufRead>(reader: R) -> ReaderLines<R> {
+        ReaderLines {
+            reader,
+            remaining: ZCString::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Create a ZCString by reading an entire file
+    ///
+    /// Regular files are read directly into a single `ArcStr` sized from the
+    /// file length. Files that report no usable length, such as pipes,
+    /// character devices and `/proc` files, are read to EOF as by
+    /// [`Self::read_all`] instead.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Construct path relative to the project root
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    /// path.push("from_file_test.txt");
+    /// let r = ZCString::from_file(&path)?;
+    /// assert_eq!(&r, "xyzzy");
+    ///
+    /// path.set_file_name("empty.txt");
+    /// assert_eq!(ZCString::from_file(&path)?, "");
+    ///
+    /// // reports a length of 0
+    /// if cfg!(target_os = "linux") {
+    ///     let status = ZCString::from_file("/proc/self/status")?;
+    ///     assert!(status.starts_with("Name:"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<ZCString, ReaderError> {
+        let mut handle = std::fs::File::open(path)?;
+        let metadata = handle.metadata()?;
+
+        if metadata.is_file() && metadata.len() > 0 {
+            Self::read_range(&mut handle, 0..)
+        } else {
+            Self::read_all(&mut handle)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::from_file`] but skips a leading UTF-8 byte order
+    /// mark, see [`Self::read_skip_bom`].
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # use std::path::PathBuf;
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    /// path.push("from_file_test.txt");
+    ///
+    /// assert_eq!(ZCString::from_file_skip_bom(&path).unwrap(), "xyzzy");
+    ///
+    /// path.set_file_name("empty.txt");
+    /// assert_eq!(ZCString::from_file_skip_bom(&path).unwrap(), "");
+    ///
+    /// // reports a length of 0
+    /// if cfg!(target_os = "linux") {
+    ///     let status = ZCString::from_file_skip_bom("/proc/self/status").unwrap();
+    ///     assert!(status.starts_with("Name:"));
+    /// }
+    /// ```
+    pub fn from_file_skip_bom<P: AsRef<std::path::Path>>(path: P) -> Result<ZCString, ReaderError> {
+        let mut handle = std::fs::File::open(path)?;
+        let metadata = handle.metadata()?;
+
+        if metadata.is_file() && metadata.len() > 0 {
+            let size = usize::try_from(metadata.len()).map_err(|_| ReaderError::TooLarge {
+                size: metadata.len(),
+                limit: usize::MAX as u64,
+            })?;
+
+            Self::read_skip_bom(&mut handle, size)
+        } else {
+            // no usable length, read to EOF first as from_file does
+            let mut buffer = Vec::new();
+            handle.read_to_end(&mut buffer)?;
+            Self::read_skip_bom(&mut buffer.as_slice(), buffer.len())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Same as [`Self::from_file`] but fails with [`ReaderError::TooLarge`]
+    /// if the file is larger than `max_bytes`. The check happens before any
+    /// buffer is allocated, making this suitable for untrusted inputs. Files
+    /// that report no usable length are read as by [`Self::read_all_limited`].
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::{ReaderError, ZCString};
+    /// # use std::path::PathBuf;
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    /// path.push("from_file_test.txt");
+    ///
+    /// assert_eq!(ZCString::from_file_limited(&path, 5).unwrap(), "xyzzy");
+    /// assert!(matches!(
+    ///     ZCString::from_file_limited(&path, 4),
+    ///     Err(ReaderError::TooLarge { size: 5, limit: 4 })
+    /// ));
+    ///
+    /// path.set_file_name("empty.txt");
+    /// assert_eq!(ZCString::from_file_limited(&path, 4).unwrap(), "");
+    ///
+    /// if cfg!(target_os = "linux") {
+    ///     let status = ZCString::from_file_limited("/proc/self/status", 1 << 20).unwrap();
+    ///     assert!(status.starts_with("Name:"));
+    /// }
+    /// ```
+    pub fn from_file_limited<P: AsRef<std::path::Path>>(
+        path: P,
+        max_bytes: u64,
+    ) -> Result<ZCString, ReaderError> {
+        let mut handle = std::fs::File::open(path)?;
+        let metadata = handle.metadata()?;
+
+        if metadata.is_file() && metadata.len() > 0 {
+            Self::read_range_limited(&mut handle, 0.., max_bytes)
+        } else {
+            Self::read_all_limited(&mut handle, max_bytes)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Create a ZCString by reading only the byte `range` of a file, as by
+    /// [`Self::read_range`]. A range reaching past the end of the file fails
+    /// with [`ReaderError::Io`].
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::{ReaderError, ZCString};
+    /// # use std::path::PathBuf;
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    /// path.push("from_file_test.txt");
+    ///
+    /// assert_eq!(ZCString::from_file_range(&path, 1..3).unwrap(), "yz");
+    /// assert_eq!(ZCString::from_file_range(&path, 2..).unwrap(), "zzy");
+    /// assert_eq!(ZCString::from_file_range(&path, 2..2).unwrap(), "");
+    /// assert!(matches!(
+    ///     ZCString::from_file_range(&path, 3..10),
+    ///     Err(ReaderError::Io(_))
+    /// ));
+    /// ```
+    pub fn from_file_range<P, R>(path: P, range: R) -> Result<ZCString, ReaderError>
+    where
+        P: AsRef<std::path::Path>,
+        R: RangeBounds<u64>,
+    {
+        let mut handle = std::fs::File::open(path)?;
+        Self::read_range(&mut handle, range)
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Async counterpart of [`Self::from_file`].
+    ///
+    /// The file is read on tokio's blocking pool using [`Self::from_file`],
+    /// so the contents are still read directly into a single `ArcStr`
+    /// allocation. Must be called from within a tokio runtime.
+    ///
+    /// The thread-local source does not survive `.await` points, a task may
+    /// resume on a different worker thread. Set the source (for example via
+    /// [`serde_json_from_zcstring`]) only after the read has completed and
+    /// never hold a [`SourceGuard`] across an `.await`.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let rt = tokio::runtime::Builder::new_current_thread().build()?;
+    /// # rt.block_on(async {
+    /// let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    /// path.push("examples");
+    /// path.push("file_example.json");
+    /// let data = ZCString::from_file_async(path).await?;
+    ///
+    /// # #[cfg(feature = "serde_json")]
+    /// # {
+    /// // parse within a single poll, no .await between setting the
+    /// // source and using the results
+    /// let value: std::collections::HashMap<ZCString, serde_json::Value> =
+    ///     zcstring::serde_json_from_zcstring(data.clone())?;
+    /// assert!(value.keys().all(|k| data.source_of(k)));
+    /// # }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # })
+    /// # }
+    /// ```
+    pub async fn from_file_async<P>(path: P) -> Result<ZCString, ReaderError>
+    where
+        P: AsRef<std::path::Path> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || Self::from_file(path))
+            .await
+            .map_err(std::io::Error::from)?
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Async counterpart of [`Self::read`]. The bytes must be valid UTF-8.
+    ///
+    /// `ArcStr` can only be filled synchronously, so the bytes are first
+    /// read into a staging buffer and then copied into a single `ArcStr`.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let rt = tokio::runtime::Builder::new_current_thread().build()?;
+    /// # rt.block_on(async {
+    /// let mut data: &[u8] = b"Cats and dogs";
+    /// let r = ZCString::read_async(&mut data, 4).await?;
+    /// assert_eq!(r, "Cats");
+    /// assert_eq!(ZCString::read_async(&mut data, 0).await?, "");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # })
+    /// # }
+    /// ```
+    pub async fn read_async<I>(input: &mut I, bytes: usize) -> Result<ZCString, ReaderError>
+    where
+        I: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        if bytes == 0 {
+            // ArcStr::init_with can't create an empty string
+            return Ok(ZCString::new());
+        }
+
+        let mut buffer = vec![0u8; bytes];
+        input.read_exact(&mut buffer).await?;
+
+        let result = ArcStr::init_with(bytes, |dest| dest.copy_from_slice(&buffer))?;
+        Ok(ZCString::from(result))
+    }
+
+    #[cfg(feature = "tokio")]
+    /// Async counterpart of [`Self::read_range`]. The range must contain
+    /// valid UTF-8.
+    ///
+    /// ### Arguments
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use zcstring::ZCString;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let rt = tokio::runtime::Builder::new_current_thread().build()?;
+    /// # rt.block_on(async {
+    /// let mut data = Cursor::new(b"Cats and dogs");
+    /// let r = ZCString::read_range_async(&mut data, 5..8).await?;
+    /// assert_eq!(r, "and");
+    ///
+    /// data.set_position(2);
+    /// assert_eq!(ZCString::read_range_async(&mut data, 5..).await?, "and dogs");
+    ///
+    /// // the start lies past the end, the position is left as is
+    /// data.set_position(8);
+    /// assert!(ZCString::read_range_async(&mut data, 20..).await.is_err());
+    /// assert_eq!(data.position(), 8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # })
+    /// # }
+    /// ```
+    pub async fn read_range_async<I, R>(input: &mut I, range: R) -> Result<ZCString, ReaderError>
+    where
+        I: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin,
+        R: RangeBounds<u64>,
+    {
+        use tokio::io::AsyncSeekExt;
+
+        // resolve both bounds against a single snapshot of the position and
+        // the end, as read_range_limited does
+        let start_pos = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => *s + 1,
+            Bound::Unbounded => input.stream_position().await?,
+        };
+
+        let end_pos = match range.end_bound() {
+            Bound::Included(e) => *e + 1,
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => {
+                let origin = match range.start_bound() {
+                    Bound::Unbounded => start_pos,
+                    _ => input.stream_position().await?,
+                };
+
+                let end = input.seek(SeekFrom::End(0)).await?;
+                input.seek(SeekFrom::Start(origin)).await?;
+                end
+            }
+        };
+
+        if start_pos > end_pos {
+            // error
+            return Err(ReaderError::InvalidRange {
+                start: start_pos,
+                end: end_pos,
+            });
+        }
+
+        if start_pos == end_pos {
+            // edge case
+            return Ok(ZCString::new());
+        }
+
+        input.seek(SeekFrom::Start(start_pos)).await?;
+        Self::read_async(input, (end_pos - start_pos) as usize).await
+    }
+}
+
+impl Default for ZCString {
+    fn default() -> Self {
+        ZCString::from(literal!(""))
+    }
+}
+
+impl PartialEq<str> for ZCString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ZCString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<ZCString> for &str {
+    fn eq(&self, other: &ZCString) -> bool {
+        self == &**other
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<String> for ZCString {
+    fn eq(&self, other: &String) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ZCString> for String {
+    fn eq(&self, other: &ZCString) -> bool {
+        *self == other.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<alloc::sync::Arc<str>> for ZCString {
+    fn eq(&self, other: &alloc::sync::Arc<str>) -> bool {
+        self.as_str() == &**other
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ZCString> for alloc::sync::Arc<str> {
+    fn eq(&self, other: &ZCString) -> bool {
+        &**self == other.as_str()
+    }
+}
+
+impl PartialEq<ArcStr> for ZCString {
+    fn eq(&self, other: &ArcStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<ZCString> for ArcStr {
+    fn eq(&self, other: &ZCString) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<Substr> for ZCString {
+    fn eq(&self, other: &Substr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<ZCString> for Substr {
+    fn eq(&self, other: &ZCString) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialOrd<ArcStr> for ZCString {
+    fn partial_cmp(&self, other: &ArcStr) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<ZCString> for ArcStr {
+    fn partial_cmp(&self, other: &ZCString) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<Substr> for ZCString {
+    fn partial_cmp(&self, other: &Substr) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<ZCString> for Substr {
+    fn partial_cmp(&self, other: &ZCString) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl Deref for ZCString {
+    type Target = Substr;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ZCString {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+/// Exposes the UTF-8 bytes to byte oriented APIs.
+///
+/// There is deliberately no matching `Borrow<[u8]>`: `str` and `[u8]` hash
+/// differently, so a `HashMap<ZCString, _>` could not be queried by bytes
+/// correctly. Query byte keyed maps with `as_ref()` instead.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use zcstring::ZCString;
+/// // e.g. a hasher's update
+/// fn checksum(data: impl AsRef<[u8]>) -> u32 {
+///     data.as_ref().iter().map(|b| *b as u32).sum()
+/// }
+///
+/// let zc = ZCString::from("cat");
+/// assert_eq!(checksum(&zc), checksum(b"cat"));
+///
+/// let mut by_bytes: HashMap<&[u8], u32> = HashMap::new();
+/// by_bytes.insert(b"cat", 1);
+/// assert_eq!(by_bytes.get(AsRef::<[u8]>::as_ref(&zc)), Some(&1));
+///
+/// let mut out = Vec::new();
+/// std::io::Write::write_all(&mut out, zc.as_ref()).unwrap();
+/// assert_eq!(out, b"cat");
+/// ```
+impl AsRef<[u8]> for ZCString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Hashes exactly as the equivalent `str`, as required for lookups through
+/// [`Borrow<str>`](std::borrow::Borrow) to find their entries. Which buffer
+/// the string lies in plays no part.
+///
+/// ```
+/// # use std::collections::hash_map::DefaultHasher;
+/// # use std::collections::HashMap;
+/// # use std::hash::{Hash, Hasher};
+/// # use zcstring::ZCString;
+/// fn hash_of(value: impl Hash) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     value.hash(&mut hasher);
+///     hasher.finish()
+/// }
+///
+/// let source = ZCString::from("cats and dogs");
+///
+/// assert_eq!(hash_of(source.substr(0..4)), hash_of("cats"));
+/// assert_eq!(hash_of(ZCString::from("")), hash_of(""));
+///
+/// let mut counts: HashMap<ZCString, i32> = HashMap::new();
+/// counts.insert(source.substr(9..), 2);
+/// assert_eq!(counts.get("dogs"), Some(&2));
+/// ```
+impl core::hash::Hash for ZCString {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Allows maps and sets keyed by `ZCString` to be queried with `&str`.
+///
+/// Lookup by `&str` is guaranteed to find the entry whose key has the same
+/// contents, whichever buffer the key lies in: `ZCString`'s `Eq`, `Ord` and
+/// `Hash` all agree with those of `str`.
+///
+/// `indexmap` and `hashbrown` look keys up through the `Equivalent` trait,
+/// whose blanket impl covers any key implementing `Borrow<str>`, so no
+/// separate `Equivalent` impl is needed.
+///
+/// ```
+/// # use std::collections::{BTreeSet, HashMap, HashSet};
+/// # use zcstring::ZCString;
+/// let source = ZCString::from("cats and dogs");
+///
+/// let set: HashSet<ZCString> = source.split(' ').map(|w| source.from_substr(w)).collect();
+/// assert!(set.contains("dogs"));
+/// assert!(set.contains(&String::from("cats")[..]));
+/// assert!(!set.contains("frogs"));
+///
+/// let sorted: BTreeSet<ZCString> = set.iter().cloned().collect();
+/// assert!(sorted.contains("and"));
+///
+/// let mut std_map = HashMap::new();
+/// std_map.insert(ZCString::from("cat"), 1);
+/// assert_eq!(std_map.get("cat"), Some(&1));
+/// assert_eq!(std_map.get("dog"), None);
+///
+/// let mut index_map = indexmap::IndexMap::new();
+/// index_map.insert(ZCString::from("cat"), 1);
+/// assert_eq!(index_map.get("cat"), Some(&1));
+///
+/// let mut hash_map = hashbrown::HashMap::new();
+/// hash_map.insert(ZCString::from("cat"), 1);
+/// assert_eq!(hash_map.get("cat"), Some(&1));
+/// assert_eq!(hash_map.get("dog"), None);
+/// ```
+impl core::borrow::Borrow<str> for ZCString {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+/// Describes `ZCString` with exactly the schema of `String`.
+///
+/// **Requires the `schemars` feature.**
+///
+/// ```
+/// mod owned {
+///     # use std::collections::HashMap;
+///     #[derive(schemars::JsonSchema)]
+///     pub struct Animal {
+///         name: String,
+///         color: Option<String>,
+///         tags: Vec<String>,
+///         counts: HashMap<String, i32>,
+///     }
+/// }
+///
+/// mod zero_copy {
+///     # use std::collections::HashMap;
+///     # use zcstring::ZCString;
+///     #[derive(schemars::JsonSchema)]
+///     pub struct Animal {
+///         name: ZCString,
+///         color: Option<ZCString>,
+///         tags: Vec<ZCString>,
+///         counts: HashMap<ZCString, i32>,
+///     }
+/// }
+///
+/// assert_eq!(
+///     schemars::schema_for!(owned::Animal),
+///     schemars::schema_for!(zero_copy::Animal)
+/// );
+/// ```
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ZCString {
+    fn inline_schema() -> bool {
+        String::inline_schema()
+    }
+
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        String::schema_id()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
+}
+
+/// Binds a `ZCString` as TEXT, borrowing its contents without a copy.
+///
+/// **Requires the `rusqlite` feature.**
+#[cfg(feature = "rusqlite")]
+impl rusqlite::ToSql for ZCString {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.as_str()))
+    }
+}
+
+/// Reads a TEXT column as by [`ZCString::from_str_with_source`], so text
+/// lying within the current source is zero-copy and anything else, such as
+/// text owned by SQLite, is copied. Invalid UTF-8 and non-TEXT values fail
+/// with a `FromSqlError`, use `Option<ZCString>` for nullable columns.
+///
+/// **Requires the `rusqlite` feature.**
+///
+/// ```
+/// # use rusqlite::Connection;
+/// # use zcstring::ZCString;
+/// let db = Connection::open_in_memory().unwrap();
+/// db.execute("CREATE TABLE pets (name TEXT, motto TEXT)", ()).unwrap();
+///
+/// let name = ZCString::from("cat");
+/// db.execute("INSERT INTO pets VALUES (?1, ?2)", (&name, ZCString::from("meow")))
+///     .unwrap();
+/// db.execute("INSERT INTO pets VALUES (?1, ?2)", ("dog", None::<ZCString>))
+///     .unwrap();
+///
+/// let mut select = db.prepare("SELECT name, motto FROM pets ORDER BY rowid").unwrap();
+/// let pets: Vec<(ZCString, Option<ZCString>)> = select
+///     .query_map((), |row| Ok((row.get(0)?, row.get(1)?)))
+///     .unwrap()
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(pets[0], (name, Some(ZCString::from("meow"))));
+/// assert_eq!(pets[1], (ZCString::from("dog"), None));
+///
+/// let invalid = db.query_row("SELECT CAST(x'ff' AS TEXT)", (), |row| row.get::<_, ZCString>(0));
+/// assert!(invalid.is_err());
+/// ```
+#[cfg(feature = "rusqlite")]
+impl rusqlite::types::FromSql for ZCString {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        value.as_str().map(ZCString::from_str_with_source)
+    }
+}
+
+impl core::fmt::Display for ZCString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl core::fmt::Debug for ZCString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// From<&str> will check for existence of &str within the current source
+//             ZCString
+impl From<&str> for ZCString {
+    #[inline]
+    fn from(s: &str) -> Self {
+        ZCString::from_str_with_source(s)
+    }
+}
+
+/// From<&mut str> behaves like From<&str>
+impl From<&mut str> for ZCString {
+    #[inline]
+    fn from(s: &mut str) -> Self {
+        ZCString::from_str_with_source(s)
+    }
+}
+
+impl From<ArcStr> for ZCString {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        ZCString(Substr::from(s))
+    }
+}
+
+/// `ArcStr` can't adopt the allocation of an `Arc<str>`, so the contents
+/// are copied once into a new `ArcStr`.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use zcstring::ZCString;
+/// let arc: Arc<str> = Arc::from("cats");
+/// let zc = ZCString::from(arc.clone());
+///
+/// assert_eq!(zc, arc);
+/// assert_eq!(arc, zc);
+/// assert_ne!(zc, Arc::<str>::from("dogs"));
+/// ```
+#[cfg(feature = "std")]
+impl From<alloc::sync::Arc<str>> for ZCString {
+    #[inline]
+    fn from(s: alloc::sync::Arc<str>) -> Self {
+        ZCString::from_str_without_source(&s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<String> for ZCString {
+    #[inline]
+    fn from(s: String) -> Self {
+        ZCString::from_str_without_source(&s)
+    }
+}
+
+// number of leading space and tab bytes of line
+fn leading_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+// length of the leading [sign] digits [. digits] [e [sign] digits] run
+fn number_prefix_len(bytes: &[u8]) -> usize {
+    let digits_from = |i: usize| {
+        bytes[i.min(bytes.len())..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+
+    let mut len = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    len += digits_from(len);
+
+    if bytes.get(len) == Some(&b'.') {
+        len += 1 + digits_from(len + 1);
+    }
+
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut exponent = len + 1;
+        exponent += usize::from(matches!(bytes.get(exponent), Some(b'+' | b'-')));
+
+        let digits = digits_from(exponent);
+        if digits > 0 {
+            len = exponent + digits;
+        }
+    }
+
+    len
+}
+
+// removes up to indent bytes of leading spaces and tabs from line
+fn strip_indent(line: &str, indent: usize) -> &str {
+    &line[indent.min(leading_indent(line))..]
+}
+
+/// Appends every item, as by [`ZCString::push_str`] but copying into a
+/// single new buffer for the whole batch. Extending with at least one
+/// non-empty item detaches this string from its previous buffer and from
+/// any source it was borrowed from.
+///
+/// ```
+/// # use zcstring::ZCString;
+/// let source = ZCString::from("cats and dogs");
+/// let mut s = source.substr(0..4);
+///
+/// s.extend([" and", " frogs"]);
+/// s.extend([ZCString::from(" and"), ZCString::from(" bats")]);
+/// s.extend(['!', '!']);
+///
+/// assert_eq!(s, "cats and frogs and bats!!");
+/// assert!(!source.source_of(&s));
+///
+/// let words: ZCString = ["cats", " ", "dogs"].into_iter().collect();
+/// assert_eq!(words, "cats dogs");
+/// ```
+impl<'a> Extend<&'a str> for ZCString {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        self.extend_with(|builder| iter.into_iter().for_each(|s| builder.push_str(s)));
+    }
+}
+
+impl Extend<char> for ZCString {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.extend_with(|builder| iter.into_iter().for_each(|c| builder.push(c)));
+    }
+}
+
+impl Extend<ZCString> for ZCString {
+    fn extend<I: IntoIterator<Item = ZCString>>(&mut self, iter: I) {
+        self.extend_with(|builder| iter.into_iter().for_each(|s| builder.push_str(&s)));
+    }
+}
+
+impl<'a> FromIterator<&'a str> for ZCString {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut result = ZCString::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl FromIterator<char> for ZCString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut result = ZCString::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl FromIterator<ZCString> for ZCString {
+    fn from_iter<I: IntoIterator<Item = ZCString>>(iter: I) -> Self {
+        let mut result = ZCString::new();
+        result.extend(iter);
+        result
+    }
+}
+
+/// An RAII guard used to manage the lifecycle of the thread-local string source.
+///
+/// Created via [`ZCString::get_source_guard`]. Dropping it, or calling
+/// [`SourceGuard::release`], removes its source from the thread-local
+/// stack, leaving any other sources in place whatever order guards are
+/// released in.
+///
+/// The guard belongs to the thread that created it and is not `Send`;
+/// dropping it on another thread would remove a source from the wrong
+/// stack. Use [`Source`] to install a source on another thread.
+///
+/// ```compile_fail
+/// # use zcstring::ZCString;
+/// let guard = ZCString::from("cats").get_source_guard();
+/// std::thread::spawn(move || drop(guard));
+/// ```
+#[cfg(feature = "std")]
+pub struct SourceGuard {
+    source: ZCString,
+    replaced: Option<ZCString>,
+    // the guard modifies the creating thread's stack, keep it there
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl SourceGuard {
+    /// Returns the source installed by this guard.
+    pub fn source(&self) -> &ZCString {
+        &self.source
+    }
+
+    /// Returns the source that was active when this guard was created,
+    /// which is active again once this guard is released, provided guards
+    /// created later have been released first.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let cats = ZCString::from("cats");
+    /// let dogs = ZCString::from("dogs");
+    ///
+    /// let cats_guard = cats.get_source_guard();
+    /// let dogs_guard = dogs.get_source_guard();
+    ///
+    /// assert_eq!(cats_guard.replaced(), None);
+    /// assert!(dogs_guard.replaced().unwrap().same_buffer(&cats));
+    /// ```
+    pub fn replaced(&self) -> Option<&ZCString> {
+        self.replaced.as_ref()
+    }
+
+    /// Removes this guard's source right away, the same as dropping the
+    /// guard, and returns the source that is active afterwards.
+    ///
+    /// Released in reverse order of creation, that is [`Self::replaced`].
+    /// Released out of order, the sources of guards created later stay
+    /// active.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::{current_source, ZCString};
+    /// let cats = ZCString::from("cats");
+    /// let dogs = ZCString::from("dogs");
+    /// let frogs = ZCString::from("frogs");
+    ///
+    /// let cats_guard = cats.get_source_guard();
+    /// let dogs_guard = dogs.get_source_guard();
+    /// let frogs_guard = frogs.get_source_guard();
+    ///
+    /// // out of order, frogs stays active
+    /// assert!(dogs_guard.release().unwrap().same_buffer(&frogs));
+    ///
+    /// // frogs replaced dogs, but dogs is already gone so cats is restored
+    /// assert!(frogs_guard.replaced().unwrap().same_buffer(&dogs));
+    /// assert!(frogs_guard.release().unwrap().same_buffer(&cats));
+    ///
+    /// assert_eq!(cats_guard.release(), None);
+    /// assert_eq!(current_source(), None);
+    /// ```
+    pub fn release(self) -> Option<ZCString> {
+        drop(self);
+        current_source()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for SourceGuard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SourceGuard")
+            .field("source", &self.source)
+            .field("replaced", &self.replaced)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for SourceGuard {
+    fn drop(&mut self) {
+        SOURCE.with(|ctx| {
+            let mut stack = ctx.borrow_mut();
+
+            // equal entries are interchangeable, remove the innermost
+            if let Some(index) = stack.iter().rposition(|source| {
+                source.as_ptr() == self.source.as_ptr() && source.len() == self.source.len()
+            }) {
+                stack.remove(index);
+            }
+        });
+    }
+}
+
+/// A handle to a source string intended to be moved to another thread and
+/// activated there.
+///
+/// The thread-local source is not inherited by spawned threads, so work
+/// moved to a worker thread would otherwise allocate for every string.
+/// Move a `Source` into the worker, or share a reference with scoped
+/// workers, and call [`Source::activate`] or [`Source::run`] to make it the
+/// worker's thread-local source. `Source` is `Send` and `Sync`, and cloning
+/// it only bumps a reference count.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{Source, ZCString};
+/// let data = ZCString::from("cats dogs frogs");
+/// let source = Source::from(data.clone());
+///
+/// let words = std::thread::spawn(move || {
+///     let _guard = source.activate();
+///     source
+///         .split(' ')
+///         .map(ZCString::from)
+///         .collect::<Vec<_>>()
+/// })
+/// .join()
+/// .unwrap();
+///
+/// // all words were created on the worker thread yet point into data
+/// assert!(words.iter().all(|w| data.source_of(w)));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Source(ZCString);
+
+#[cfg(feature = "std")]
+impl Source {
+    /// Creates a new `Source` handle for `source`.
+    pub fn new(source: ZCString) -> Self {
+        Source(source)
+    }
+
+    /// Installs this source as a thread-local source of the current thread,
+    /// until the returned guard is dropped.
+    pub fn activate(&self) -> SourceGuard {
+        self.0.get_source_guard()
+    }
+
+    /// Runs `f` with this source installed on the current thread, as by
+    /// [`ZCString::with_source`]. Convenient from worker closures, for
+    /// example those passed to `std::thread::scope` or rayon.
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::{Source, ZCString};
+    /// let data = ZCString::from("cats dogs\nfrogs bats\nrats gnats\n");
+    /// let source = Source::from(data.clone());
+    ///
+    /// let words: Vec<ZCString> = std::thread::scope(|scope| {
+    ///     let workers: Vec<_> = data
+    ///         .lines()
+    ///         .map(|line| {
+    ///             let source = &source;
+    ///             scope.spawn(move || {
+    ///                 source.run(|| line.split(' ').map(ZCString::from).collect::<Vec<_>>())
+    ///             })
+    ///         })
+    ///         .collect();
+    ///
+    ///     workers
+    ///         .into_iter()
+    ///         .flat_map(|worker| worker.join().unwrap())
+    ///         .collect()
+    /// });
+    ///
+    /// assert_eq!(words.len(), 6);
+    /// assert!(words.iter().all(|word| data.source_of(word)));
+    /// ```
+    pub fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _guard = self.activate();
+        f()
+    }
+
+    /// Returns the wrapped source string.
+    pub fn into_inner(self) -> ZCString {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deref for Source {
+    type Target = ZCString;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ZCString> for Source {
+    #[inline]
+    fn from(s: ZCString) -> Self {
+        Source(s)
+    }
+}
+
+/// A [`ZCString`] compared and hashed ignoring ASCII case, for use as a
+/// `HashMap` or `HashSet` key. Ordering is not provided as there is no
+/// case-insensitive order consistent with the wrapped string's.
+///
+/// ### Example
+/// ```
+/// # use std::collections::HashMap;
+/// # use zcstring::{CaseInsensitive, ZCString};
+/// let source = ZCString::from("Content-Type: text/plain\nX-Cats: 2");
+///
+/// let headers: HashMap<CaseInsensitive, ZCString> = source
+///     .lines_zc()
+///     .filter_map(|line| {
+///         let (key, value) = line.split_once(": ")?;
+///         Some((CaseInsensitive(line.from_substr(key)), line.from_substr(value)))
+///     })
+///     .collect();
+///
+/// let key = CaseInsensitive::from(ZCString::from("content-type"));
+/// assert_eq!(headers[&key], "text/plain");
+/// assert_eq!(headers[&CaseInsensitive("X-CATS".into())], "2");
+///
+/// // the original spelling is kept
+/// assert!(headers.keys().any(|key| key.0 == "Content-Type"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CaseInsensitive(pub ZCString);
+
+impl CaseInsensitive {
+    /// Returns the wrapped string.
+    pub fn into_inner(self) -> ZCString {
+        self.0
+    }
+}
+
+impl PartialEq for CaseInsensitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CaseInsensitive {}
+
+impl core::hash::Hash for CaseInsensitive {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // fold case the same way as eq, terminated like str's hash
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl Deref for CaseInsensitive {
+    type Target = ZCString;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<ZCString> for CaseInsensitive {
+    #[inline]
+    fn from(s: ZCString) -> Self {
+        CaseInsensitive(s)
+    }
+}
+
+impl core::fmt::Display for CaseInsensitive {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// A snapshot of a thread's sources taken by [`source_handle`], converting
+/// strings like [`ZCString::from_str_with_source`] without looking up the
+/// thread-local source for every string.
+///
+/// The snapshot keeps its sources alive and doesn't follow later changes to
+/// the thread-local sources. To convert slices of one known source use
+/// [`ZCString::substrs_from`] or [`ZCString::from_substr`] instead.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct SourceRef {
+    sources: Vec<ZCString>,
+}
+
+#[cfg(feature = "std")]
+impl SourceRef {
+    /// Creates a `ZCString` from `s`, zero-copy if it lies within one of
+    /// the snapshot's sources, the innermost first, otherwise copied.
+    pub fn convert(&self, s: &str) -> ZCString {
+        match self.sources.iter().rev().find(|source| source.source_of(s)) {
+            Some(source) => source.from_substr(s),
+            None => fallback_alloc(s),
+        }
+    }
+
+    /// Returns `true` if the snapshot holds no sources, every conversion
+    /// then copies.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+/// Incrementally builds a [`ZCString`], implementing [`std::fmt::Write`]
+/// so `write!` can be used.
+///
+/// ### Example
+/// ```
+/// # use std::fmt::Write;
+/// # use zcstring::ZCStringBuilder;
+/// let mut builder = ZCStringBuilder::new();
+/// builder.push_str("cats");
+/// builder.push(' ');
+/// write!(builder, "and {} dogs", 2).unwrap();
+///
+/// assert_eq!(builder.build(), "cats and 2 dogs");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ZCStringBuilder {
+    buffer: String,
+}
+
+impl ZCStringBuilder {
+    /// Creates a new, empty builder.
+    pub fn new() -> Self {
+        ZCStringBuilder::default()
+    }
+
+    /// Creates a new builder able to hold `capacity` bytes before
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ZCStringBuilder {
+            buffer: String::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a string slice.
+    pub fn push_str(&mut self, s: &str) {
+        self.buffer.push_str(s);
+    }
+
+    /// Appends a single character.
+    pub fn push(&mut self, c: char) {
+        self.buffer.push(c);
+    }
+
+    /// Returns the number of bytes accumulated so far.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if nothing has been accumulated.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns the number of bytes the builder can hold before reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Reserves room for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional);
+    }
+
+    /// Consumes the builder, producing a `ZCString` backed by a single
+    /// `ArcStr` of exactly the accumulated length.
+    pub fn build(self) -> ZCString {
+        if self.buffer.is_empty() {
+            ZCString::new()
+        } else {
+            ZCString::from_str_without_source(&self.buffer)
+        }
+    }
+}
+
+impl core::fmt::Write for ZCStringBuilder {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+/// Only strings are accepted. Use [`lenient`] or [`lenient_option`] on
+/// fields that may also hold numbers or booleans.
+///
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Record {
+///     id: ZCString,
+/// }
+///
+/// let quoted: Record = serde_json_from_zcstring(r#"{"id": "123"}"#.into()).unwrap();
+/// assert_eq!(quoted.id, "123");
+///
+/// let number = serde_json_from_zcstring::<Record>(r#"{"id": 123}"#.into());
+/// assert!(number.is_err());
+/// # }
+/// ```
+///
+/// Strings stay zero-copy when serde buffers values internally, as it does
+/// for `#[serde(flatten)]` and internally tagged enums. Only strings that
+/// had to be unescaped are copied.
+///
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use arcstr::literal;
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Inner {
+///     plain: ZCString,
+///     escaped: ZCString,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Outer {
+///     id: ZCString,
+///     #[serde(flatten)]
+///     inner: Inner,
+/// }
+///
+/// #[derive(Deserialize)]
+/// #[serde(tag = "type")]
+/// enum Message {
+///     Animal { name: ZCString },
+/// }
+///
+/// let json = ZCString::from(literal!(r#"{"id": "1", "plain": "cat", "escaped": "\"dog\""}"#));
+/// let outer: Outer = serde_json_from_zcstring(json.clone()).unwrap();
+/// assert!(json.source_of(&outer.id));
+/// assert!(json.source_of(&outer.inner.plain));
+/// assert!(!json.source_of(&outer.inner.escaped));
+///
+/// let json = ZCString::from(literal!(r#"{"type": "Animal", "name": "cat"}"#));
+/// let Message::Animal { name } = serde_json_from_zcstring(json.clone()).unwrap();
+/// assert!(json.source_of(&name));
+/// # }
+/// ```
+///
+/// The source stays active across sequences, so every element of a
+/// `Vec<ZCString>`, tuple or tuple struct borrows from it too.
+///
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Pair(ZCString, ZCString);
+///
+/// let json = ZCString::from(r#"["a", "b", "c"]"#);
+/// let list: Vec<ZCString> = serde_json_from_zcstring(json.clone()).unwrap();
+/// assert_eq!(list, ["a", "b", "c"]);
+/// assert!(list.iter().all(|s| json.source_of(s)));
+///
+/// let (a, b, c): (ZCString, ZCString, ZCString) = serde_json_from_zcstring(json.clone()).unwrap();
+/// assert!([a, b, c].iter().all(|s| json.source_of(s)));
+///
+/// let json = ZCString::from(r#"["cat", "dog"]"#);
+/// let Pair(cat, dog) = serde_json_from_zcstring(json.clone()).unwrap();
+/// assert!(json.source_of(&cat) && json.source_of(&dog));
+/// # }
+/// ```
+///
+/// Map keys borrow from the source as well, including maps collected by
+/// `#[serde(flatten)]`. serde_json hands unescaped keys over as borrowed
+/// strings, keys containing escapes are decoded and so allocated, just
+/// like values.
+///
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use serde::Deserialize;
+/// # use std::collections::BTreeMap;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Record {
+///     id: ZCString,
+///     #[serde(flatten)]
+///     rest: BTreeMap<ZCString, serde_json::Value>,
+/// }
+///
+/// let json = ZCString::from(r#"{"id": "1", "cat": 1, "dog": {"legs": 4}, "fr\"og": 2}"#);
+///
+/// let map: BTreeMap<ZCString, serde_json::Value> = serde_json_from_zcstring(json.clone()).unwrap();
+/// let record: Record = serde_json_from_zcstring(json.clone()).unwrap();
+///
+/// for keys in [map.keys(), record.rest.keys()] {
+///     for key in keys {
+///         assert_eq!(json.source_of(key), !key.contains("\""));
+///     }
+/// }
+/// assert_eq!(record.rest.len(), 3);
+/// # }
+/// ```
+///
+/// Binary formats such as `postcard` and `bincode` round-trip `ZCString`
+/// too. Their strings are borrowed from the binary buffer, which is
+/// generally not UTF-8 and so can't be a source, so each string is copied.
+/// Only when the encoded buffer happens to be valid UTF-8 can it be wrapped
+/// in a `ZCString` source to make them zero-copy.
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use zcstring::ZCString;
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Animal {
+///     name: ZCString,
+///     sound: ZCString,
+/// }
+///
+/// let cat = Animal {
+///     name: ZCString::from("cat"),
+///     sound: ZCString::from("meow"),
+/// };
+///
+/// let bytes = postcard::to_allocvec(&cat).unwrap();
+/// assert_eq!(postcard::from_bytes::<Animal>(&bytes).unwrap(), cat);
+///
+/// let bytes = bincode::serialize(&cat).unwrap();
+/// assert_eq!(bincode::deserialize::<Animal>(&bytes).unwrap(), cat);
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// // postcard prefixes short strings with a single byte length, here
+/// // "\x03cat\x04meow", which is valid UTF-8
+/// let source = ZCString::from(std::str::from_utf8(&postcard::to_allocvec(&cat).unwrap()).unwrap());
+/// let animal: Animal = ZCString::with_source(source.clone(), |source| {
+///     postcard::from_bytes(source.as_bytes()).unwrap()
+/// });
+/// assert!(source.source_of(&animal.name) && source.source_of(&animal.sound));
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ZCString {
+    /// Custom deserializer that attempts to borrow from the thread-local source
+    /// when encountering a string.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_zcstring(deserializer, ZCStringVisitor { source: None })
+    }
+}
+
+/// A [`serde::de::DeserializeSeed`] producing a [`ZCString`] that borrows
+/// from an explicitly given source instead of the thread-local one.
+///
+/// Useful where deserialization may hop threads, or to avoid the
+/// thread-local source altogether. Derived `Deserialize` impls can't take a
+/// seed, so this is meant for hand written `DeserializeSeed`/`Visitor`
+/// impls or top level values, see [`serde_json_from_zcstring_seed`].
+///
+/// ### Example
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use zcstring::{serde_json_from_zcstring_seed, ZCString, ZCStringSeed};
+/// let json = ZCString::from(r#""no thread-local source needed""#);
+///
+/// // no source guard is active here
+/// let value = serde_json_from_zcstring_seed(&json, ZCStringSeed(&json)).unwrap();
+///
+/// assert_eq!(value, "no thread-local source needed");
+/// assert!(json.source_of(&value));
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug)]
+pub struct ZCStringSeed<'a>(pub &'a ZCString);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::DeserializeSeed<'de> for ZCStringSeed<'_> {
+    type Value = ZCString;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_zcstring(
+            deserializer,
+            ZCStringVisitor {
+                source: Some(self.0),
+            },
+        )
+    }
+}
+
+/// A table of interned strings, handing out one shared `ZCString` for all
+/// equal values.
+///
+/// The first occurrence of a value is stored as a [`ZCString::detach`]ed
+/// copy in a buffer of its own. Repeated values such as statuses or enum
+/// like fields then share that one small buffer instead of each pinning
+/// the document they were parsed from, so records can be detached from the
+/// document cheaply. Use [`ZCInterner::seed`] to intern while deserializing.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{ZCInterner, ZCString};
+/// let document = ZCString::from("active inactive active active");
+/// let mut interner = ZCInterner::new();
+///
+/// let statuses: Vec<ZCString> = document
+///     .split(' ')
+///     .map(|status| interner.intern(status))
+///     .collect();
+///
+/// assert_eq!(interner.len(), 2);
+/// assert!(statuses[0].same_buffer(&statuses[2]));
+/// assert!(!statuses[0].same_buffer(&statuses[1]));
+/// assert!(!document.same_buffer(&statuses[0]));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct ZCInterner {
+    strings: std::collections::HashSet<ZCString>,
+}
+
+#[cfg(feature = "std")]
+impl ZCInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared `ZCString` equal to `s`, storing a detached copy
+    /// of `s` if this is its first occurrence.
+    pub fn intern(&mut self, s: &str) -> ZCString {
+        if let Some(interned) = self.strings.get(s) {
+            return interned.clone();
+        }
+
+        let interned = ZCString::from_str_without_source(s);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if nothing has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    #[cfg(feature = "serde")]
+    /// Returns a [`serde::de::DeserializeSeed`] deserializing a string and
+    /// interning it in this table.
+    ///
+    /// ### Example
+    /// ```
+    /// # #[cfg(feature = "serde_json")]
+    /// # {
+    /// # use serde::de::{DeserializeSeed, Deserializer, SeqAccess, Visitor};
+    /// # use zcstring::{ZCInterner, ZCString};
+    /// // a list of strings, each interned
+    /// struct Interned<'a>(&'a mut ZCInterner);
+    ///
+    /// impl<'de> DeserializeSeed<'de> for Interned<'_> {
+    ///     type Value = Vec<ZCString>;
+    ///
+    ///     fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+    ///         d.deserialize_seq(self)
+    ///     }
+    /// }
+    ///
+    /// impl<'de> Visitor<'de> for Interned<'_> {
+    ///     type Value = Vec<ZCString>;
+    ///
+    ///     fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    ///         f.write_str("a list of strings")
+    ///     }
+    ///
+    ///     fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    ///         let mut values = Vec::new();
+    ///         while let Some(value) = seq.next_element_seed(self.0.seed())? {
+    ///             values.push(value);
+    ///         }
+    ///         Ok(values)
+    ///     }
+    /// }
+    ///
+    /// let statuses = ["active", "inactive", "pending"];
+    /// let json = format!(
+    ///     "[{}]",
+    ///     (0..10_000)
+    ///         .map(|i| format!("\"{}\"", statuses[i % 3]))
+    ///         .collect::<Vec<_>>()
+    ///         .join(",")
+    /// );
+    ///
+    /// let mut interner = ZCInterner::new();
+    /// let values = Interned(&mut interner)
+    ///     .deserialize(&mut serde_json::Deserializer::from_str(&json))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(values.len(), 10_000);
+    /// assert_eq!(interner.len(), 3);
+    ///
+    /// // every "inactive" shares the buffer of the first one
+    /// assert!((1..10_000).step_by(3).all(|i| values[i].same_buffer(&values[1])));
+    /// # }
+    /// ```
+    pub fn seed(&mut self) -> ZCInternSeed<'_> {
+        ZCInternSeed(self)
+    }
+}
+
+/// A [`serde::de::DeserializeSeed`] producing a `ZCString` interned in a
+/// [`ZCInterner`], see [`ZCInterner::seed`].
+#[cfg(all(feature = "serde", feature = "std"))]
+#[derive(Debug)]
+pub struct ZCInternSeed<'a>(&'a mut ZCInterner);
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<'de> serde::de::DeserializeSeed<'de> for ZCInternSeed<'_> {
+    type Value = ZCString;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = ZCString::deserialize(deserializer)?;
+        Ok(self.0.intern(&value))
+    }
+}
+
+// shared by the Deserialize and DeserializeSeed impls
+#[cfg(feature = "serde")]
+fn deserialize_zcstring<'de, D>(
+    deserializer: D,
+    visitor: ZCStringVisitor<'_>,
+) -> Result<ZCString, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // deserialize using our visitor
+    deserializer.deserialize_str(visitor)
+}
+
+// borrows from source if given, otherwise from the thread-local source
+#[cfg(feature = "serde")]
+struct ZCStringVisitor<'a> {
+    source: Option<&'a ZCString>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for ZCStringVisitor<'_> {
+    type Value = ZCString;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a string that can be borrowed or owned")
+    }
+
+    // borrow will build an arcstr::Substr of the original JSON
+    fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match self.source {
+            Some(source) if source.source_of(s) => Ok(source.from_substr(s)),
+            Some(_) => fallback_de(s),
+            #[cfg(feature = "std")]
+            None => ZCString::try_from_source(s).map_or_else(|| fallback_de(s), Ok),
+            #[cfg(not(feature = "std"))]
+            None => fallback_de(s),
+        }
+    }
+
+    // the string may still lie within the source, for example when
+    // replayed from serde's internal buffering, otherwise build an
+    // arcstr::Substr based on the full ArcStr of our decoded string
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match self.source {
+            Some(source) if source.source_of(s) => Ok(source.from_substr(s)),
+            Some(_) => fallback_de(s),
+            #[cfg(feature = "std")]
+            None => ZCString::try_from_source(s).map_or_else(|| fallback_de(s), Ok),
+            #[cfg(not(feature = "std"))]
+            None => fallback_de(s),
+        }
+    }
+
+    // build an arcstr::Substr based on the full ArcStr of our
+    // decoded string
+    fn visit_string<E>(self, s: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(s.as_str())
+    }
+}
+
+/// Lenient deserializer for `ZCString` fields, for use with
+/// `#[serde(deserialize_with = "zcstring::lenient")]`.
+///
+/// Accepts strings (zero-copy when possible) as well as integers, floats
+/// and booleans, which are formatted into an owned `ZCString`, and `null`,
+/// which becomes an empty `ZCString`. Floats use Rust's shortest round-trip
+/// formatting, so whole numbers have no trailing `.0` (`8080.0` becomes
+/// `"8080"`). Requires a self-describing format such as JSON.
+///
+/// See [`lenient_option`] for `Option<ZCString>` fields.
+///
+/// ### Example
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Record {
+///     #[serde(deserialize_with = "zcstring::lenient")]
+///     id: ZCString,
+/// }
+///
+/// let parse = |json: &str| serde_json_from_zcstring::<Record>(json.into()).unwrap().id;
+///
+/// assert_eq!(parse(r#"{"id": "abc"}"#), "abc");
+/// assert_eq!(parse(r#"{"id": 8080}"#), "8080");
+/// assert_eq!(parse(r#"{"id": -7}"#), "-7");
+/// assert_eq!(parse(r#"{"id": 18446744073709551615}"#), "18446744073709551615");
+/// assert_eq!(parse(r#"{"id": 1.5}"#), "1.5");
+/// assert_eq!(parse(r#"{"id": 8080.0}"#), "8080");
+/// assert_eq!(parse(r#"{"id": true}"#), "true");
+/// assert_eq!(parse(r#"{"id": null}"#), "");
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+pub fn lenient<'de, D>(deserializer: D) -> Result<ZCString, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(LenientVisitor)
+}
+
+/// Lenient deserializer for `Option<ZCString>` fields, for use with
+/// `#[serde(default, deserialize_with = "zcstring::lenient_option")]`.
+///
+/// Same as [`lenient`] except `null` becomes `None`.
+///
+/// ### Example
+/// ```
+/// # #[cfg(feature = "serde_json")]
+/// # {
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Record {
+///     #[serde(default, deserialize_with = "zcstring::lenient_option")]
+///     port: Option<ZCString>,
+/// }
+///
+/// let parse = |json: &str| serde_json_from_zcstring::<Record>(json.into()).unwrap().port;
+///
+/// assert_eq!(parse(r#"{"port": 8080}"#).unwrap(), "8080");
+/// assert_eq!(parse(r#"{"port": "http"}"#).unwrap(), "http");
+/// assert_eq!(parse(r#"{"port": null}"#), None);
+/// assert_eq!(parse(r#"{}"#), None);
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+pub fn lenient_option<'de, D>(deserializer: D) -> Result<Option<ZCString>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct LenientOptionVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for LenientOptionVisitor {
+        type Value = Option<ZCString>;
+
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("a string, number, boolean or null")
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            lenient(deserializer).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(LenientOptionVisitor)
+}
+
+// accepts any scalar, strings are handled like ZCStringVisitor
+#[cfg(feature = "serde")]
+struct LenientVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for LenientVisitor {
+    type Value = ZCString;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a string, number, boolean or null")
+    }
+
+    fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        serde::de::Visitor::visit_borrowed_str(ZCStringVisitor { source: None }, s)
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        serde::de::Visitor::visit_str(ZCStringVisitor { source: None }, s)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::from(if v {
+            literal!("true")
+        } else {
+            literal!("false")
+        }))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::from_str_without_source(&v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::from_str_without_source(&v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::from_str_without_source(&v.to_string()))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::new())
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(ZCString::new())
+    }
+}
+
+/// Parses a JSON string into type `T` while using the provided `ZCString` as
+/// the context for any zero-copy deserialization.
+///
+/// **Requires the `serde_json` feature.**
+#[cfg(feature = "serde_json")]
+pub fn serde_json_from_zcstring<T>(json: ZCString) -> Result<T, serde_json::Error>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    ZCString::with_source(json, |j| serde_json::from_str::<T>(&j))
+}
+
+/// Parses `json` with an explicit [`serde::de::DeserializeSeed`] instead of
+/// the thread-local source. Pair it with [`ZCStringSeed`] or a custom seed
+/// holding a reference to `json`, no thread-local state is involved.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::de::{DeserializeSeed, Deserializer, SeqAccess, Visitor};
+/// # use zcstring::{serde_json_from_zcstring_seed, ZCString, ZCStringSeed};
+/// // a seed for a list of strings, borrowing each from the same source
+/// struct ListSeed<'a>(&'a ZCString);
+///
+/// impl<'de> DeserializeSeed<'de> for ListSeed<'_> {
+///     type Value = Vec<ZCString>;
+///
+///     fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+///         d.deserialize_seq(self)
+///     }
+/// }
+///
+/// impl<'de> Visitor<'de> for ListSeed<'_> {
+///     type Value = Vec<ZCString>;
+///
+///     fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         f.write_str("a list of strings")
+///     }
+///
+///     fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+///         let mut items = Vec::new();
+///         while let Some(item) = seq.next_element_seed(ZCStringSeed(self.0))? {
+///             items.push(item);
+///         }
+///         Ok(items)
+///     }
+/// }
+///
+/// let json = ZCString::from(r#"["cats", "dogs"]"#);
+/// let list = serde_json_from_zcstring_seed(&json, ListSeed(&json)).unwrap();
+///
+/// assert_eq!(list, ["cats", "dogs"]);
+/// assert!(list.iter().all(|s| json.source_of(s)));
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn serde_json_from_zcstring_seed<'a, S>(
+    json: &'a ZCString,
+    seed: S,
+) -> Result<S::Value, serde_json::Error>
+where
+    S: serde::de::DeserializeSeed<'a>,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json.as_str());
+    let value = seed.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// The raw, unparsed JSON text of a value, kept as a [`ZCString`].
+///
+/// Deserialized within [`serde_json_from_zcstring`] the raw text is a
+/// zero-copy slice of the source, escapes included, as it is never decoded.
+/// Use [`ZCRawValue::parse`] to deserialize it later, strings within it then
+/// still borrow from the original source.
+///
+/// Built on `serde_json::value::RawValue`, so the deserializer must be able
+/// to borrow from its input.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCRawValue, ZCString};
+/// #[derive(Deserialize)]
+/// struct Envelope {
+///     kind: ZCString,
+///     payload: ZCRawValue,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Animal {
+///     name: ZCString,
+/// }
+///
+/// let json = ZCString::from(r#"{"kind": "animal", "payload": {"name": "cat \"tom\""}}"#);
+/// let envelope: Envelope = serde_json_from_zcstring(json.clone()).unwrap();
+///
+/// assert_eq!(envelope.payload.get(), r#"{"name": "cat \"tom\""}"#);
+/// assert!(json.source_of(envelope.payload.as_zcstring()));
+///
+/// let animal: Animal = envelope.payload.parse().unwrap();
+/// assert_eq!(animal.name, "cat \"tom\"");
+///
+/// let json = ZCString::from(r#"{"kind": "animal", "payload": {"name": "cat"}}"#);
+/// let envelope: Envelope = serde_json_from_zcstring(json.clone()).unwrap();
+/// let animal: Animal = envelope.payload.parse().unwrap();
+/// assert!(json.source_of(&animal.name));
+/// ```
+#[cfg(feature = "serde_json")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZCRawValue(ZCString);
+
+#[cfg(feature = "serde_json")]
+impl ZCRawValue {
+    /// Returns the raw JSON text as a `ZCString`.
+    pub fn as_zcstring(&self) -> &ZCString {
+        &self.0
     }
-}
 
-#[cfg(feature = "std")]
-impl PartialEq<ZCString> for String {
-    fn eq(&self, other: &ZCString) -> bool {
-        *self == other.0
+    /// Returns the raw JSON text.
+    pub fn get(&self) -> &str {
+        self.0.as_str()
     }
-}
 
-impl Deref for ZCString {
-    type Target = Substr;
+    /// Consumes the value, returning the raw JSON text.
+    pub fn into_zcstring(self) -> ZCString {
+        self.0
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    /// Parses the raw JSON text into `T` as by [`serde_json_from_zcstring`].
+    pub fn parse<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        serde_json_from_zcstring(self.0.clone())
     }
 }
 
-impl AsRef<str> for ZCString {
-    fn as_ref(&self) -> &str {
-        self
+/// Captures the exact span of the value, whitespace, nesting and escapes
+/// included, without deserializing it.
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_zcstring, ZCRawValue, ZCString};
+/// #[derive(Deserialize)]
+/// struct Event {
+///     id: u32,
+///     detail: ZCRawValue,
+/// }
+///
+/// let nested = r#"{ "user": {"name": "larry", "pets": ["cat", "dog"]},
+///     "tags": [ {"k": "a\u0062c"} ] }"#;
+/// let json = ZCString::from(format!(r#"{{"id": 7, "detail": {nested}, "more": null}}"#));
+/// let event: Event = serde_json_from_zcstring(json.clone()).unwrap();
+///
+/// // verbatim and zero-copy
+/// assert_eq!(event.id, 7);
+/// assert_eq!(event.detail.get(), nested);
+/// assert!(event.detail.as_zcstring().is_view_of(&json));
+///
+/// // deferred until needed
+/// let value: serde_json::Value = event.detail.parse().unwrap();
+/// assert_eq!(value["tags"][0]["k"], "abc");
+/// ```
+#[cfg(feature = "serde_json")]
+impl<'de> Deserialize<'de> for ZCRawValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = <&'de serde_json::value::RawValue>::deserialize(deserializer)?;
+        Ok(ZCRawValue(ZCString::from_str_with_source(raw.get())))
     }
 }
 
-impl std::borrow::Borrow<str> for ZCString {
-    fn borrow(&self) -> &str {
-        self
-    }
+/// Parses a YAML string into type `T` while using the provided `ZCString` as
+/// the context for any zero-copy deserialization.
+///
+/// Plain (unquoted) scalars are borrowed from `yaml`, quoted, block and
+/// folded scalars generally need processing and are copied.
+///
+/// **Requires the `serde_yaml` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_yaml_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Config {
+///     plain: ZCString,
+///     quoted: ZCString,
+/// }
+///
+/// let yaml = ZCString::from("plain: cats and dogs\nquoted: \"quoted \\n value\"\n");
+/// let config: Config = serde_yaml_from_zcstring(yaml.clone()).unwrap();
+///
+/// assert_eq!(config.plain, "cats and dogs");
+/// assert!(yaml.source_of(&config.plain));
+///
+/// assert_eq!(config.quoted, "quoted \n value");
+/// assert!(!yaml.source_of(&config.quoted));
+/// ```
+#[cfg(feature = "serde_yaml")]
+pub fn serde_yaml_from_zcstring<T>(yaml: ZCString) -> Result<T, serde_yaml::Error>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    ZCString::with_source(yaml, |y| serde_yaml::from_str::<T>(&y))
 }
 
-impl std::fmt::Display for ZCString {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
-    }
+/// Parses MessagePack `bytes` into type `T` with `text_source` as the
+/// context for zero-copy deserialization.
+///
+/// MessagePack strings are borrowed from `bytes`, so they are zero-copy
+/// only if `bytes` lies within `text_source`, otherwise each string is
+/// copied as with plain `rmp_serde::from_slice`. As a `ZCString` holds
+/// UTF-8 this applies only to payloads embedded in a text buffer, arbitrary
+/// MessagePack is generally not valid UTF-8 and can't be a source itself.
+///
+/// **Requires the `rmp` feature.**
+///
+/// ### Example
+/// ```
+/// # use zcstring::{rmp_from_slice_with_source, ZCString};
+/// // "£cat" is encoded as C2 A3 63 61 74, and A3 63 61 74 happens to be
+/// // the MessagePack encoding of the string "cat"
+/// let text = ZCString::from("£cat");
+/// let payload = &text.as_bytes()[1..];
+///
+/// let cat: ZCString = rmp_from_slice_with_source(payload, &text).unwrap();
+/// assert_eq!(cat, "cat");
+/// assert!(text.source_of(&cat));
+///
+/// // the same payload outside the source is copied
+/// let copy = payload.to_vec();
+/// let cat: ZCString = rmp_from_slice_with_source(&copy, &text).unwrap();
+/// assert_eq!(cat, "cat");
+/// assert!(!text.source_of(&cat));
+/// ```
+#[cfg(feature = "rmp")]
+pub fn rmp_from_slice_with_source<T>(
+    bytes: &[u8],
+    text_source: &ZCString,
+) -> Result<T, rmp_serde::decode::Error>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    ZCString::with_source(text_source.clone(), |_| rmp_serde::from_slice::<T>(bytes))
 }
 
-impl std::fmt::Debug for ZCString {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(&self.0, f)
-    }
+/// Parses a TOML string into type `T` while using the provided `ZCString` as
+/// the context for any zero-copy deserialization.
+///
+/// Keys and strings that need no unescaping, such as basic strings without
+/// escapes and literal strings, are borrowed from `toml`, everything else
+/// is copied.
+///
+/// **Requires the `toml` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use std::collections::BTreeMap;
+/// # use zcstring::{toml_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: ZCString,
+///     server: Server,
+///     labels: BTreeMap<ZCString, ZCString>,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Server {
+///     host: ZCString,
+///     root: ZCString,
+///     banner: ZCString,
+/// }
+///
+/// let toml = ZCString::from(
+///     r#"
+/// name = "cats"
+///
+/// [server]
+/// host = "localhost"
+/// root = 'C:\srv'
+/// banner = "hello\tworld"
+///
+/// [labels]
+/// color = "red"
+/// "#,
+/// );
+/// let config: Config = toml_from_zcstring(toml.clone()).unwrap();
+///
+/// assert!(toml.source_of(&config.name));
+/// assert!(toml.source_of(&config.server.host));
+/// assert!(toml.source_of(&config.server.root));
+/// let (key, value) = config.labels.iter().next().unwrap();
+/// assert!(toml.source_of(key) && toml.source_of(value));
+///
+/// // escapes need decoding
+/// assert_eq!(config.server.banner, "hello\tworld");
+/// assert!(!toml.source_of(&config.server.banner));
+/// ```
+#[cfg(feature = "toml")]
+pub fn toml_from_zcstring<T>(toml: ZCString) -> Result<T, toml::de::Error>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    ZCString::with_source(toml, |t| toml::from_str::<T>(&t))
 }
 
-/// From<&str> will check for existence of &str within the current source
-//             ZCString
-impl From<&str> for ZCString {
-    #[inline]
-    fn from(s: &str) -> Self {
-        ZCString::from_str_with_source(s)
-    }
+/// Reads a TOML file into a `ZCString` and parses it as by
+/// [`toml_from_zcstring`].
+///
+/// **Requires the `toml` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{toml_from_file_zcstring, ZCError, ZCString};
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: ZCString,
+/// }
+///
+/// let missing = toml_from_file_zcstring::<Config, _>("no/such/Config.toml");
+/// assert!(matches!(missing, Err(ZCError::Reader(_))));
+/// ```
+#[cfg(feature = "toml")]
+pub fn toml_from_file_zcstring<T, P>(path: P) -> Result<T, ZCError>
+where
+    T: for<'de> Deserialize<'de>,
+    P: AsRef<std::path::Path>,
+{
+    Ok(toml_from_zcstring(ZCString::from_file(path)?)?)
 }
 
-impl From<ArcStr> for ZCString {
-    #[inline]
-    fn from(s: ArcStr) -> Self {
-        ZCString(Substr::from(s))
-    }
+/// Serializes `value` as JSON directly into a new `ZCString`.
+///
+/// The JSON is written to a byte buffer and then copied into a single
+/// `ArcStr`, avoiding the extra `String` of `serde_json::to_string`.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use zcstring::{serde_json_from_zcstring, serde_json_to_zcstring, ZCString};
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Animal {
+///     animal: ZCString,
+///     color: ZCString,
+/// }
+///
+/// let cat = Animal {
+///     animal: ZCString::from("cat"),
+///     color: ZCString::from("red \"ish\""),
+/// };
+///
+/// let json = serde_json_to_zcstring(&cat).unwrap();
+/// assert_eq!(json, r#"{"animal":"cat","color":"red \"ish\""}"#);
+/// assert_eq!(serde_json_from_zcstring::<Animal>(json).unwrap(), cat);
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn serde_json_to_zcstring<T>(value: &T) -> Result<ZCString, serde_json::Error>
+where
+    T: ?Sized + Serialize,
+{
+    bytes_to_zcstring(serde_json::to_vec(value)?)
 }
 
-#[cfg(feature = "std")]
-impl From<String> for ZCString {
-    #[inline]
-    fn from(s: String) -> Self {
-        ZCString::from_str_without_source(&s)
-    }
+/// Serializes `value` as pretty-printed JSON directly into a new
+/// `ZCString`. See [`serde_json_to_zcstring`].
+///
+/// **Requires the `serde_json` feature.**
+#[cfg(feature = "serde_json")]
+pub fn serde_json_to_zcstring_pretty<T>(value: &T) -> Result<ZCString, serde_json::Error>
+where
+    T: ?Sized + Serialize,
+{
+    bytes_to_zcstring(serde_json::to_vec_pretty(value)?)
 }
 
-/// An RAII guard used to manage the lifecycle of the thread-local string source.
+/// Serializes `value` as JSON straight into `writer`, without building an
+/// intermediate `String`. A thin wrapper around `serde_json::to_writer`,
+/// `ZCString` fields are written as plain JSON strings.
 ///
-/// Created via [`ZCString::get_source_guard`].
-pub struct SourceGuard {
-    old_source: Option<ZCString>,
+/// Wrap unbuffered writers such as files or sockets in a
+/// [`std::io::BufWriter`], serde_json issues many small writes.
+///
+/// **Requires the `serde_json` and `std` features.**
+///
+/// ### Example
+/// ```
+/// # use serde::Serialize;
+/// # use zcstring::{serde_json_to_writer, ZCString};
+/// #[derive(Serialize)]
+/// struct Entry {
+///     host: ZCString,
+///     message: ZCString,
+///     tags: Vec<ZCString>,
+/// }
+///
+/// let entry = Entry {
+///     host: ZCString::from("cats.example.com"),
+///     message: ZCString::from("cat \"on\" keyboard"),
+///     tags: vec![ZCString::from("cat"), ZCString::from("keyboard")],
+/// };
+///
+/// let mut out = Vec::new();
+/// serde_json_to_writer(&mut out, &entry).unwrap();
+///
+/// assert_eq!(
+///     out,
+///     br#"{"host":"cats.example.com","message":"cat \"on\" keyboard","tags":["cat","keyboard"]}"#
+/// );
+/// ```
+#[cfg(all(feature = "serde_json", feature = "std"))]
+pub fn serde_json_to_writer<T, W>(writer: &mut W, value: &T) -> Result<(), serde_json::Error>
+where
+    T: ?Sized + Serialize,
+    W: ?Sized + std::io::Write,
+{
+    serde_json::to_writer(writer, value)
 }
 
-impl Drop for SourceGuard {
-    fn drop(&mut self) {
-        SOURCE.with(|ctx| {
-            let mut borrow = ctx.borrow_mut();
-            std::mem::swap(&mut *borrow, &mut self.old_source);
-        });
+// copy serde_json output into a single ArcStr
+#[cfg(feature = "serde_json")]
+fn bytes_to_zcstring(bytes: Vec<u8>) -> Result<ZCString, serde_json::Error> {
+    let result = ArcStr::init_with(bytes.len(), |buffer| buffer.copy_from_slice(&bytes))
+        .map_err(serde::ser::Error::custom)?;
+    Ok(ZCString::from(result))
+}
+
+/// Parses newline-delimited JSON (NDJSON), yielding one `T` per non-blank
+/// line. Every record uses its line as the context for zero-copy
+/// deserialization, so all records borrow from the one shared `source`.
+///
+/// Errors report the 1-based line number of the failing record, iteration
+/// continues with the next line.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_lines_from_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct LogEntry {
+///     level: ZCString,
+/// }
+///
+/// let source = ZCString::from("{\"level\": \"error\"}\n\n{\"level\": \"info\"}\nnot json\n");
+/// let mut records = serde_json_lines_from_zcstring::<LogEntry>(source.clone());
+///
+/// let first = records.next().unwrap().unwrap();
+/// assert_eq!(first.level, "error");
+/// assert!(source.source_of(&first.level));
+///
+/// assert_eq!(records.next().unwrap().unwrap().level, "info");
+/// assert_eq!(records.next().unwrap().err().unwrap().line, 4);
+/// assert!(records.next().is_none());
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn serde_json_lines_from_zcstring<T>(source: ZCString) -> ZCJsonLines<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    ZCJsonLines {
+        remaining: source,
+        line: 0,
+        _marker: core::marker::PhantomData,
     }
 }
 
+/// Error for a single record of newline-delimited JSON.
 #[cfg(feature = "serde_json")]
-impl<'de> Deserialize<'de> for ZCString {
-    /// Custom deserializer that attempts to borrow from the thread-local source
-    /// when encountering a string.
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        struct ZCStringVisitor;
+#[derive(thiserror::Error, Debug)]
+#[error("JSON failure on line {line}: {source}")]
+pub struct ZCJsonLineError {
+    /// 1-based line number of the failing record.
+    pub line: usize,
+    /// The underlying parse error.
+    pub source: serde_json::Error,
+}
 
-        impl<'de> serde::de::Visitor<'de> for ZCStringVisitor {
-            type Value = ZCString;
+/// Iterator over the records of newline-delimited JSON.
+///
+/// Created via [`serde_json_lines_from_zcstring`].
+#[cfg(feature = "serde_json")]
+pub struct ZCJsonLines<T> {
+    remaining: ZCString,
+    line: usize,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("a string that can be borrowed or owned")
-            }
+#[cfg(feature = "serde_json")]
+impl<T> Iterator for ZCJsonLines<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    type Item = Result<T, ZCJsonLineError>;
 
-            // borrow will build an arcstr::Substr of the original JSON
-            fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(ZCString::from_str_with_source(s))
-            }
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.remaining.is_empty() {
+            let end = self
+                .remaining
+                .find('\n')
+                .map_or(self.remaining.len(), |e| e + 1);
+            let record = self.remaining.substr(..end);
+            self.remaining = self.remaining.substr(end..);
+            self.line += 1;
 
-            // build an arcstr::Substr based on the full ArcStr of our
-            // decoded string
-            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(ZCString::from_str_without_source(s))
+            // skip blank lines
+            if record.trim().is_empty() {
+                continue;
             }
 
-            // build an arcstr::Substr based on the full ArcStr of our
-            // decoded string
-            fn visit_string<E>(self, s: String) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                self.visit_str(s.as_str())
-            }
+            return Some(
+                serde_json_from_zcstring(record).map_err(|source| ZCJsonLineError {
+                    line: self.line,
+                    source,
+                }),
+            );
         }
 
-        // deserialize using our visitor
-        deserializer.deserialize_str(ZCStringVisitor)
+        None
     }
 }
 
-/// Parses a JSON string into type `T` while using the provided `ZCString` as
-/// the context for any zero-copy deserialization.
+/// Reads everything from `reader` into a single buffer and parses it as JSON
+/// into type `T`, using the buffer as the context for any zero-copy
+/// deserialization.
 ///
-/// **Requires the `serde` feature.**
-#[cfg(feature = "serde_json")]
-pub fn serde_json_from_zcstring<T>(json: ZCString) -> Result<T, serde_json::Error>
+/// **Requires the `serde_json` and `std` features.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_reader_zcstring, ZCString};
+/// #[derive(Deserialize)]
+/// struct Animal {
+///     animal: ZCString,
+///     color: ZCString,
+/// }
+///
+/// let json = r#"{"animal": "cat", "color": "red"}"#;
+/// let parsed: Animal = serde_json_from_reader_zcstring(json.as_bytes()).unwrap();
+///
+/// // both fields point into the one buffer holding the whole document
+/// assert_eq!(parsed.animal.parent(), json);
+/// assert!(arcstr::ArcStr::ptr_eq(parsed.animal.parent(), parsed.color.parent()));
+/// ```
+#[cfg(all(feature = "serde_json", feature = "std"))]
+pub fn serde_json_from_reader_zcstring<T, R>(mut reader: R) -> Result<T, ZCError>
 where
     T: for<'de> Deserialize<'de>,
+    R: Read,
 {
-    ZCString::with_source(json, |j| serde_json::from_str::<T>(&j))
+    let json = ZCString::read_all(&mut reader)?;
+    Ok(serde_json_from_zcstring(json)?)
+}
+
+/// Reads an entire file and parses it as JSON into type `T`, using the file
+/// contents as the context for any zero-copy deserialization.
+///
+/// **Requires the `serde_json` and `std` features.**
+///
+/// ### Example
+/// ```
+/// # use std::collections::HashMap;
+/// # use zcstring::{serde_json_from_file_zcstring, ZCString};
+/// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/file_example.json");
+/// let parsed: HashMap<ZCString, serde_json::Value> =
+///     serde_json_from_file_zcstring(path).unwrap();
+///
+/// let contents = std::fs::read_to_string(path).unwrap();
+/// assert!(parsed.keys().all(|k| k.parent() == contents.as_str()));
+/// ```
+#[cfg(all(feature = "serde_json", feature = "std"))]
+pub fn serde_json_from_file_zcstring<T, P>(path: P) -> Result<T, ZCError>
+where
+    T: for<'de> Deserialize<'de>,
+    P: AsRef<std::path::Path>,
+{
+    let json = ZCString::from_file(path)?;
+    Ok(serde_json_from_zcstring(json)?)
+}
+
+/// Copies `bytes` into a single buffer and parses it as JSON into type `T`,
+/// using the buffer as the context for any zero-copy deserialization.
+///
+/// Invalid UTF-8 is reported as [`ZCError::Utf8`].
+///
+/// **Requires the `serde_json` and `std` features.**
+///
+/// ### Example
+/// ```
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_slice_zcstring, ZCError, ZCString};
+/// #[derive(Deserialize)]
+/// struct Animal {
+///     animal: ZCString,
+///     color: ZCString,
+/// }
+///
+/// let body: &[u8] = br#"{"animal": "cat", "color": "red"}"#;
+/// let parsed: Animal = serde_json_from_slice_zcstring(body).unwrap();
+///
+/// // both fields point into the one buffer holding the whole body
+/// assert_eq!(parsed.animal.parent().as_bytes(), body);
+/// assert!(arcstr::ArcStr::ptr_eq(parsed.animal.parent(), parsed.color.parent()));
+///
+/// let invalid = serde_json_from_slice_zcstring::<Animal>(b"\xff");
+/// assert!(matches!(invalid, Err(ZCError::Utf8(_))));
+///
+/// let empty = serde_json_from_slice_zcstring::<Animal>(b"");
+/// assert!(matches!(empty, Err(ZCError::Json(_))));
+/// ```
+#[cfg(all(feature = "serde_json", feature = "std"))]
+pub fn serde_json_from_slice_zcstring<T>(bytes: &[u8]) -> Result<T, ZCError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    if bytes.is_empty() {
+        // ArcStr::init_with can't create an empty string
+        return Ok(serde_json_from_zcstring(ZCString::new())?);
+    }
+
+    // init_with validates the copied bytes as UTF-8
+    let json = ArcStr::init_with(bytes.len(), |buffer| buffer.copy_from_slice(bytes))?;
+    Ok(serde_json_from_zcstring(ZCString::from(json))?)
+}
+
+/// Same as [`serde_json_from_slice_zcstring`] but consumes a `Vec<u8>`,
+/// validating it as UTF-8 in place before the single copy into the source
+/// buffer.
+///
+/// **Requires the `serde_json` and `std` features.**
+///
+/// ### Example
+/// ```
+/// # use std::collections::HashMap;
+/// # use zcstring::{serde_json_from_vec_zcstring, ZCString};
+/// let body = br#"{"cat": "meow"}"#.to_vec();
+/// let parsed: HashMap<ZCString, ZCString> = serde_json_from_vec_zcstring(body).unwrap();
+/// assert_eq!(parsed[&ZCString::from("cat")], "meow");
+/// ```
+#[cfg(all(feature = "serde_json", feature = "std"))]
+pub fn serde_json_from_vec_zcstring<T>(bytes: Vec<u8>) -> Result<T, ZCError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let json = String::from_utf8(bytes).map_err(|e| e.utf8_error())?;
+    Ok(serde_json_from_zcstring(
+        ZCString::from_str_without_source(&json),
+    )?)
+}
+
+/// Converts `body` into a single buffer with [`ZCString::try_from_bytes`]
+/// and parses it as JSON into type `T`, using the buffer as the context for
+/// any zero-copy deserialization.
+///
+/// Invalid UTF-8 is reported as [`ZCError::Utf8`].
+///
+/// **Requires the `bytes` and `serde_json` features.**
+///
+/// ### Example
+/// ```
+/// # use bytes::Bytes;
+/// # use serde::Deserialize;
+/// # use zcstring::{serde_json_from_bytes_zcstring, ZCError, ZCString};
+/// #[derive(Deserialize)]
+/// struct Animal {
+///     animal: ZCString,
+///     color: ZCString,
+/// }
+///
+/// let body = Bytes::from_static(br#"{"animal": "cat", "color": "red"}"#);
+/// let parsed: Animal = serde_json_from_bytes_zcstring(body.clone()).unwrap();
+///
+/// // both fields point into the one buffer holding the whole body
+/// assert_eq!(parsed.animal.parent().as_bytes(), &body[..]);
+/// assert!(arcstr::ArcStr::ptr_eq(parsed.animal.parent(), parsed.color.parent()));
+///
+/// let invalid = serde_json_from_bytes_zcstring::<Animal>(Bytes::from_static(b"\xff"));
+/// assert!(matches!(invalid, Err(ZCError::Utf8(_))));
+///
+/// let empty = serde_json_from_bytes_zcstring::<Animal>(Bytes::new());
+/// assert!(matches!(empty, Err(ZCError::Json(_))));
+/// ```
+#[cfg(all(feature = "bytes", feature = "serde_json"))]
+pub fn serde_json_from_bytes_zcstring<T>(body: bytes::Bytes) -> Result<T, ZCError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    Ok(serde_json_from_zcstring(ZCString::try_from_bytes(body)?)?)
 }
 
 /// str iterator wrapper automatically converts &str to ZCString
@@ -568,7 +5369,7 @@ where
 pub struct ZCStringIterWrapper<'a, I> {
     source: ZCString,
     inner: I,
-    _marker: std::marker::PhantomData<&'a str>,
+    _marker: core::marker::PhantomData<&'a str>,
 }
 
 impl<'a, I> Iterator for ZCStringIterWrapper<'a, I>
@@ -583,3 +5384,212 @@ where
             .map(|slice| self.source.from_substr(slice))
     }
 }
+
+/// Iterator over char boundary respecting chunks of a [`ZCString`].
+///
+/// Created via [`ZCString::byte_chunks`].
+pub struct ByteChunks {
+    remaining: ZCString,
+    max_bytes: usize,
+}
+
+impl Iterator for ByteChunks {
+    type Item = ZCString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let mut end = self.max_bytes.min(self.remaining.len());
+        while !self.remaining.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        if end == 0 {
+            // max_bytes is smaller than the first char, take it whole
+            end = self.remaining.chars().next().map_or(0, char::len_utf8);
+        }
+
+        let chunk = self.remaining.substr(..end);
+        self.remaining = self.remaining.substr(end..);
+        Some(chunk)
+    }
+}
+
+/// Iterator over overlapping character windows of a [`ZCString`].
+///
+/// Created via [`ZCString::char_windows`].
+pub struct CharWindows {
+    // starts at the current window
+    remaining: ZCString,
+    // end of the current window, None when done
+    end: Option<usize>,
+}
+
+impl Iterator for CharWindows {
+    type Item = ZCString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+        let window = self.remaining.substr(..end);
+        let first = window.chars().next().map_or(0, char::len_utf8);
+
+        // slide both ends forward by one char
+        self.end = self.remaining[end..]
+            .chars()
+            .next()
+            .map(|c| end + c.len_utf8() - first);
+        self.remaining = self.remaining.substr(first..);
+
+        Some(window)
+    }
+}
+
+/// Iterator over the lines of a `BufRead`, yielding [`ZCString`]s that share
+/// one allocation per chunk read.
+///
+/// Created via [`ZCString::lines_from_reader`].
+#[cfg(feature = "std")]
+pub struct ReaderLines<R> {
+    reader: R,
+    // complete lines of the current chunk, always ends with a newline
+    remaining: ZCString,
+    // bytes of a line not yet terminated by a newline
+    pending: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> ReaderLines<R> {
+    // read the next chunk of complete lines into remaining, returns false
+    // at end of input
+    fn refill(&mut self) -> Result<bool, ReaderError> {
+        loop {
+            let buffer = self.reader.fill_buf()?;
+
+            if buffer.is_empty() {
+                if self.pending.is_empty() {
+                    return Ok(false);
+                }
+
+                // final line without a newline
+                let pending = core::mem::take(&mut self.pending);
+                let result = ArcStr::init_with(pending.len(), |dest| {
+                    dest.copy_from_slice(&pending);
+                })?;
+                self.remaining = ZCString::from(result);
+                return Ok(true);
+            }
+
+            match buffer.iter().rposition(|b| *b == b'\n') {
+                Some(last) => {
+                    // everything up to and including the last newline
+                    let used = last + 1;
+                    let pending = &self.pending;
+                    let result = ArcStr::init_with(pending.len() + used, |dest| {
+                        dest[..pending.len()].copy_from_slice(pending);
+                        dest[pending.len()..].copy_from_slice(&buffer[..used]);
+                    });
+
+                    self.reader.consume(used);
+                    self.pending.clear();
+                    self.remaining = ZCString::from(result?);
+                    return Ok(true);
+                }
+                None => {
+                    // no complete line yet, keep reading
+                    let used = buffer.len();
+                    self.pending.extend_from_slice(buffer);
+                    self.reader.consume(used);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> Iterator for ReaderLines<R> {
+    type Item = Result<ZCString, ReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            match self.refill() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        let end = self.remaining.find('\n');
+        let next = end.map_or(self.remaining.len(), |e| e + 1);
+        let mut line = self.remaining.substr(..end.unwrap_or(self.remaining.len()));
+        self.remaining = self.remaining.substr(next..);
+
+        // as str::lines, only a "\r\n" ending is stripped
+        if end.is_some() && line.ends_with("\r") {
+            line = line.substr(..line.len() - 1);
+        }
+
+        Some(Ok(line))
+    }
+}
+
+/// Asserts that each field is a zero-copy view into `source`, see
+/// [`ZCString::source_of`]. Fields may be anything that derefs to `str`.
+///
+/// On failure panics with the field's expression and value, optionally
+/// followed by a custom message as with `assert!`.
+///
+/// ### Example
+/// ```
+/// # use zcstring::{assert_zero_copy, ZCString};
+/// let source = ZCString::from("cats and dogs");
+/// let (cats, dogs) = source.split_once(" and ").unwrap();
+///
+/// assert_zero_copy!(source, source.from_substr(cats));
+/// assert_zero_copy!(source, dogs, "dogs should borrow from {:?}", source);
+/// ```
+///
+/// A copy with equal contents is not a view:
+/// ```should_panic
+/// # use zcstring::{assert_zero_copy, ZCString};
+/// let source = ZCString::from("cats and dogs");
+/// let copy = ZCString::from_str_without_source("cats");
+///
+/// // panics: `copy` ("cats") is not a zero-copy view of `source`
+/// assert_zero_copy!(source, copy);
+/// ```
+#[macro_export]
+macro_rules! assert_zero_copy {
+    ($source:expr, $field:expr $(,)?) => {
+        match (&$source, &$field) {
+            (source, field) => {
+                let field: &str = field;
+                if !$crate::ZCString::source_of(source, field) {
+                    panic!(
+                        "`{}` ({:?}) is not a zero-copy view of `{}`",
+                        stringify!($field),
+                        field,
+                        stringify!($source),
+                    );
+                }
+            }
+        }
+    };
+    ($source:expr, $field:expr, $($arg:tt)+) => {
+        match (&$source, &$field) {
+            (source, field) => {
+                let field: &str = field;
+                if !$crate::ZCString::source_of(source, field) {
+                    panic!(
+                        "`{}` ({:?}) is not a zero-copy view of `{}`: {}",
+                        stringify!($field),
+                        field,
+                        stringify!($source),
+                        format_args!($($arg)+),
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! Extraction of single JSON values by path without a full parse into types.
+
+use crate::ZCString;
+use serde::de::{DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+/// Returns the value addressed by the JSON Pointer (RFC 6901) `pointer`
+/// within the JSON document `source`, or `None` if there is no such value.
+///
+/// String values are returned decoded, as a zero-copy slice of `source`
+/// unless they contain escapes. Any other value (numbers, booleans, null,
+/// objects and arrays) is returned as its raw JSON text, always zero-copy.
+///
+/// As with `serde_json::Value::pointer`, `""` addresses the whole document
+/// and a non-empty pointer must start with `/`. Only the addressed value is
+/// decoded, the rest of the document is merely validated.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use zcstring::{zc_json_pointer, ZCString};
+/// let json = ZCString::from(
+///     r#"{"data": {"items": [{"name": "cat"}, {"name": "\"dog\""}], "a/b": 1, "m~n": true}}"#,
+/// );
+///
+/// let name = zc_json_pointer(&json, "/data/items/0/name").unwrap().unwrap();
+/// assert_eq!(name, "cat");
+/// assert!(json.source_of(&name));
+///
+/// // escaped strings are decoded into a new allocation
+/// let name = zc_json_pointer(&json, "/data/items/1/name").unwrap().unwrap();
+/// assert_eq!(name, "\"dog\"");
+/// assert!(!json.source_of(&name));
+///
+/// // '~1' and '~0' escape '/' and '~' in keys
+/// assert_eq!(zc_json_pointer(&json, "/data/a~1b").unwrap().unwrap(), "1");
+/// assert_eq!(zc_json_pointer(&json, "/data/m~0n").unwrap().unwrap(), "true");
+///
+/// // non-string values are returned as raw JSON text
+/// let item = zc_json_pointer(&json, "/data/items/0").unwrap().unwrap();
+/// assert_eq!(item, r#"{"name": "cat"}"#);
+///
+/// // missing paths
+/// assert_eq!(zc_json_pointer(&json, "/data/items/2").unwrap(), None);
+/// assert_eq!(zc_json_pointer(&json, "/data/nothing").unwrap(), None);
+/// assert_eq!(zc_json_pointer(&json, "/data/items/0/name/more").unwrap(), None);
+/// assert_eq!(zc_json_pointer(&json, "data").unwrap(), None);
+///
+/// // the document must still be valid JSON
+/// assert!(zc_json_pointer(&ZCString::from(r#"{"a": 1"#), "/a").is_err());
+/// ```
+pub fn zc_json_pointer(
+    source: &ZCString,
+    pointer: &str,
+) -> Result<Option<ZCString>, serde_json::Error> {
+    if pointer.is_empty() {
+        return zc_json_get_str(source, &[]);
+    }
+
+    let Some(pointer) = pointer.strip_prefix('/') else {
+        return Ok(None);
+    };
+
+    let tokens: Vec<String> = pointer
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+
+    zc_json_get_str(source, &tokens)
+}
+
+/// Same as [`zc_json_pointer`] but with the path given as a list of object
+/// keys and array indices, without any escaping.
+///
+/// **Requires the `serde_json` feature.**
+///
+/// ### Example
+/// ```
+/// # use zcstring::{zc_json_get_str, ZCString};
+/// let json = ZCString::from(r#"{"data": {"items": ["cat", "dog"], "a/b": "frog"}}"#);
+///
+/// let dog = zc_json_get_str(&json, &["data", "items", "1"]).unwrap().unwrap();
+/// assert_eq!(dog, "dog");
+/// assert!(json.source_of(&dog));
+///
+/// assert_eq!(zc_json_get_str(&json, &["data", "a/b"]).unwrap().unwrap(), "frog");
+/// assert_eq!(zc_json_get_str(&json, &["data", "items", "01"]).unwrap(), None);
+/// ```
+pub fn zc_json_get_str(
+    source: &ZCString,
+    path: &[&str],
+) -> Result<Option<ZCString>, serde_json::Error> {
+    ZCString::with_source(source.clone(), |json| {
+        let mut deserializer = serde_json::Deserializer::from_str(json.as_str());
+        let value = PathSeed { path }.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+    })
+}
+
+// follows path, returning the value found at its end
+struct PathSeed<'p> {
+    path: &'p [&'p str],
+}
+
+impl<'de> DeserializeSeed<'de> for PathSeed<'_> {
+    type Value = Option<ZCString>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !self.path.is_empty() {
+            return deserializer.deserialize_any(self);
+        }
+
+        let raw = <&'de RawValue>::deserialize(deserializer)?.get();
+
+        if raw.starts_with('"') {
+            // borrows from the active source unless escaped
+            serde_json::from_str::<ZCString>(raw)
+                .map(Some)
+                .map_err(D::Error::custom)
+        } else {
+            Ok(Some(ZCString::from_str_with_source(raw)))
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for PathSeed<'_> {
+    type Value = Option<ZCString>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = None;
+
+        while let Some(key) = map.next_key::<ZCString>()? {
+            if result.is_none() && key == self.path[0] {
+                result = map.next_value_seed(PathSeed {
+                    path: &self.path[1..],
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut result = None;
+
+        if let Some(index) = parse_index(self.path[0]) {
+            for _ in 0..index {
+                if seq.next_element::<IgnoredAny>()?.is_none() {
+                    return Ok(None);
+                }
+            }
+
+            result = seq
+                .next_element_seed(PathSeed {
+                    path: &self.path[1..],
+                })?
+                .flatten();
+        }
+
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(result)
+    }
+
+    // scalars have nothing left to follow the path into
+
+    fn visit_bool<E: Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E: Error>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E: Error>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E: Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E: Error>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+}
+
+// array index as accepted by JSON Pointer, no sign or leading zeros
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    token.parse().ok()
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `proptest` strategies producing `ZCString`s.
+//!
+//! `ZCString` implements `proptest::arbitrary::Arbitrary` like `String`, so
+//! structs holding `ZCString`s work with `any::<T>()` and
+//! `#[derive(Arbitrary)]`. The strategies here add control over the
+//! generated text, and pairs of a source and a slice of it for testing code
+//! that depends on zero-copy behavior.
+//!
+//! **Requires the `proptest` feature.**
+//!
+//! ### Example
+//! ```
+//! # use proptest::prelude::*;
+//! # use proptest::test_runner::{Config, TestRunner};
+//! # use zcstring::proptest::source_and_substr;
+//! # use zcstring::ZCString;
+//! # let config = Config { failure_persistence: None, ..Config::default() };
+//! let mut runner = TestRunner::new(config);
+//!
+//! runner
+//!     .run(&source_and_substr(), |(source, substr)| {
+//!         // empty slices at the very end don't count as within the source
+//!         prop_assume!(!substr.is_empty());
+//!         prop_assert!(source.source_of(&substr));
+//!
+//!         // from_substr on a slice of the source is zero-copy
+//!         let again = source.from_substr(&substr);
+//!         prop_assert!(source.source_of(&again));
+//!         prop_assert_eq!(&again, &substr);
+//!
+//!         // as is picking it up through the thread-local source
+//!         let borrowed = ZCString::with_source(source.clone(), |_| {
+//!             ZCString::from_str_with_source(substr.as_str())
+//!         });
+//!         prop_assert!(source.source_of(&borrowed));
+//!
+//!         // an equal copy elsewhere is not
+//!         let copy = ZCString::from_str_without_source(substr.as_str());
+//!         prop_assert!(!source.source_of(&copy));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::ZCString;
+use ::proptest::arbitrary::{any, any_with, Arbitrary, StrategyFor};
+use ::proptest::strategy::{Just, Map, Strategy};
+use ::proptest::string::{string_regex, Error, StringParam};
+
+/// Generates strings the same way as `String`'s `Arbitrary`, the
+/// parameter being a regular expression the strings match.
+impl Arbitrary for ZCString {
+    type Parameters = StringParam;
+    type Strategy = Map<StrategyFor<String>, fn(String) -> ZCString>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        any_with::<String>(args).prop_map(ZCString::from)
+    }
+}
+
+/// Returns a strategy producing arbitrary `ZCString`s, the same as
+/// `any::<ZCString>()`.
+pub fn any_zcstring() -> impl Strategy<Value = ZCString> {
+    any::<ZCString>()
+}
+
+/// Returns a strategy producing `ZCString`s matching the regular expression
+/// `regex`, which controls both the characters used and the length.
+///
+/// ### Example
+/// ```
+/// # use proptest::strategy::{Strategy, ValueTree};
+/// # use proptest::test_runner::TestRunner;
+/// # use zcstring::proptest::zcstring_regex;
+/// let strategy = zcstring_regex("[a-f0-9]{8,16}").unwrap();
+/// let value = strategy.new_tree(&mut TestRunner::default()).unwrap().current();
+///
+/// assert!((8..=16).contains(&value.len()));
+/// assert!(value.chars().all(|c| c.is_ascii_hexdigit()));
+/// ```
+// same error as proptest's own string_regex
+#[allow(clippy::result_large_err)]
+pub fn zcstring_regex(regex: &str) -> Result<impl Strategy<Value = ZCString>, Error> {
+    Ok(string_regex(regex)?.prop_map(ZCString::from))
+}
+
+/// Returns a strategy producing pairs of an arbitrary source and a
+/// zero-copy slice of it, see [`source_and_substr_from`].
+pub fn source_and_substr() -> impl Strategy<Value = (ZCString, ZCString)> {
+    source_and_substr_from(any_zcstring())
+}
+
+/// Returns a strategy producing pairs of a source taken from `sources` and
+/// a zero-copy slice of it. Slices start and end on char boundaries and may
+/// be empty or the whole source.
+pub fn source_and_substr_from<S>(sources: S) -> impl Strategy<Value = (ZCString, ZCString)>
+where
+    S: Strategy<Value = ZCString>,
+{
+    sources
+        .prop_flat_map(|source| {
+            let boundaries = source.char_indices().count() + 1;
+            (Just(source), 0..boundaries, 0..boundaries)
+        })
+        .prop_map(|(source, a, b)| {
+            let boundary = |n: usize| {
+                source
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .nth(n)
+                    .unwrap_or(source.len())
+            };
+            let (start, end) = (boundary(a.min(b)), boundary(a.max(b)));
+            let substr = source.substr(start..end);
+            (source, substr)
+        })
+}
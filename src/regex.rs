@@ -0,0 +1,71 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `regex` integration yielding zero-copy capture groups.
+
+use crate::ZCString;
+use ::regex::{Captures, Regex};
+
+/// Capture groups of a regex match, each returned as a zero-copy
+/// sub-slice of the searched [`ZCString`].
+///
+/// Created via [`captures_zc`].
+pub struct ZCCaptures<'a> {
+    hay: &'a ZCString,
+    captures: Captures<'a>,
+}
+
+impl<'a> ZCCaptures<'a> {
+    /// Returns capture group `i`, group 0 is the whole match. Returns `None`
+    /// if the group did not participate in the match.
+    pub fn get(&self, i: usize) -> Option<ZCString> {
+        self.captures.get(i).map(|m| self.hay.substr(m.range()))
+    }
+
+    /// Returns the capture group named `name`. Returns `None` if there is no
+    /// such group or it did not participate in the match.
+    pub fn name(&self, name: &str) -> Option<ZCString> {
+        self.captures.name(name).map(|m| self.hay.substr(m.range()))
+    }
+
+    /// Returns the number of capture groups, including group 0.
+    pub fn len(&self) -> usize {
+        self.captures.len()
+    }
+
+    /// Always `false`, group 0 is always present.
+    pub fn is_empty(&self) -> bool {
+        self.captures.len() == 0
+    }
+}
+
+/// Searches `hay` with `re` and returns the capture groups of the first
+/// match as zero-copy [`ZCString`]s.
+///
+/// **Requires the `regex` feature.**
+///
+/// ### Example
+/// ```
+/// # use regex::Regex;
+/// # use zcstring::{captures_zc, ZCString};
+/// let re = Regex::new(r"(?P<level>\w+): (?P<message>.*)").unwrap();
+/// let hay = ZCString::from("error: cat on keyboard");
+///
+/// let caps = captures_zc(&re, &hay).unwrap();
+/// let level = caps.name("level").unwrap();
+/// let message = caps.get(2).unwrap();
+///
+/// assert_eq!(level, "error");
+/// assert_eq!(message, "cat on keyboard");
+/// assert!(hay.source_of(&level) && hay.source_of(&message));
+/// ```
+pub fn captures_zc<'a>(re: &Regex, hay: &'a ZCString) -> Option<ZCCaptures<'a>> {
+    re.captures(hay.as_str())
+        .map(|captures| ZCCaptures { hay, captures })
+}
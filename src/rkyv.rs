@@ -0,0 +1,83 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `rkyv` archiving of `ZCString` as an `ArchivedString`.
+
+use crate::ZCString;
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::string::{ArchivedString, StringResolver};
+use ::rkyv::{Archive, Deserialize, DeserializeUnsized, Place, Serialize, SerializeUnsized};
+
+/// Archived as an `ArchivedString`, so archives are interchangeable with
+/// ones holding `String`s.
+///
+/// Deserializing goes through [`ZCString::from_str_with_source`], strings
+/// lying within the current source are zero-copy. As with other binary
+/// formats that only applies if the archive buffer is itself within a
+/// `ZCString`, which requires it to be valid UTF-8, so in general strings
+/// are copied. For true zero-copy access read the archive in place.
+///
+/// **Requires the `rkyv` feature.**
+///
+/// ### Example
+/// ```
+/// # use rkyv::rancor::Error;
+/// # use zcstring::ZCString;
+/// #[derive(Debug, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+/// struct LogLine {
+///     host: ZCString,
+///     level: ZCString,
+///     message: ZCString,
+/// }
+///
+/// let line = LogLine {
+///     host: ZCString::from("cats.example.com"),
+///     level: ZCString::from("info"),
+///     message: ZCString::from("cat on keyboard"),
+/// };
+///
+/// let bytes = rkyv::to_bytes::<Error>(&line).unwrap();
+///
+/// // read in place
+/// let archived = rkyv::access::<ArchivedLogLine, Error>(&bytes).unwrap();
+/// assert_eq!(archived.message, "cat on keyboard");
+///
+/// // or deserialize
+/// let reloaded = rkyv::from_bytes::<LogLine, Error>(&bytes).unwrap();
+/// assert_eq!(reloaded, line);
+/// ```
+impl Archive for ZCString {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self.as_str(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for ZCString
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<D> Deserialize<ZCString, D> for ArchivedString
+where
+    D: Fallible + ?Sized,
+    str: DeserializeUnsized<str, D>,
+{
+    fn deserialize(&self, _: &mut D) -> Result<ZCString, D::Error> {
+        Ok(ZCString::from_str_with_source(self.as_str()))
+    }
+}
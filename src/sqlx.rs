@@ -0,0 +1,155 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! `sqlx` `Type`, `Encode` and `Decode` for `ZCString`, with the same SQL
+//! types as `String`.
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+    use crate::ZCString;
+    use ::sqlx::encode::IsNull;
+    use ::sqlx::error::BoxDynError;
+    use ::sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+    use ::sqlx::{Decode, Encode, Type};
+
+    /// Same SQL types as `String` (`TEXT`, `VARCHAR`, `CHAR`, `NAME` and
+    /// `citext`), so `ZCString` and `Option<ZCString>` can be used directly
+    /// in `FromRow` structs and as query arguments.
+    ///
+    /// Encoding writes the string straight into the argument buffer without
+    /// an intermediate copy. Decoding goes through
+    /// [`ZCString::from_str_with_source`], allocating one buffer for the
+    /// value.
+    ///
+    /// **Requires the `sqlx-postgres` feature.**
+    impl Type<Postgres> for ZCString {
+        fn type_info() -> PgTypeInfo {
+            <&str as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <&str as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl PgHasArrayType for ZCString {
+        fn array_type_info() -> PgTypeInfo {
+            <&str as PgHasArrayType>::array_type_info()
+        }
+
+        fn array_compatible(ty: &PgTypeInfo) -> bool {
+            <&str as PgHasArrayType>::array_compatible(ty)
+        }
+    }
+
+    impl Encode<'_, Postgres> for ZCString {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <&str as Encode<Postgres>>::encode(self.as_str(), buf)
+        }
+    }
+
+    impl<'r> Decode<'r, Postgres> for ZCString {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(ZCString::from_str_with_source(value.as_str()?))
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use crate::ZCString;
+    use ::sqlx::encode::IsNull;
+    use ::sqlx::error::BoxDynError;
+    use ::sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+    use ::sqlx::{Decode, Encode, Type};
+    use std::borrow::Cow;
+
+    /// Same SQL type as `String` (`TEXT`), so `ZCString` and
+    /// `Option<ZCString>` can be used directly in `FromRow` structs and as
+    /// query arguments.
+    ///
+    /// SQLite arguments must outlive the query, so like `String` encoding
+    /// copies the string into the argument list. Decoding goes through
+    /// [`ZCString::from_str_with_source`], allocating one buffer for the
+    /// value.
+    ///
+    /// **Requires the `sqlx-sqlite` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// # let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    /// # rt.block_on(async {
+    /// #[derive(Debug, PartialEq, sqlx::FromRow)]
+    /// struct Pet {
+    ///     name: ZCString,
+    ///     owner: Option<ZCString>,
+    /// }
+    ///
+    /// let mut conn = <sqlx::SqliteConnection as sqlx::Connection>::connect("sqlite::memory:")
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// sqlx::query("CREATE TABLE pets (name TEXT NOT NULL, owner TEXT)")
+    ///     .execute(&mut conn)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for (name, owner) in [("cat", Some("larry")), ("dog", None)] {
+    ///     sqlx::query("INSERT INTO pets (name, owner) VALUES (?, ?)")
+    ///         .bind(ZCString::from(name))
+    ///         .bind(owner.map(ZCString::from))
+    ///         .execute(&mut conn)
+    ///         .await
+    ///         .unwrap();
+    /// }
+    ///
+    /// let pets: Vec<Pet> = sqlx::query_as("SELECT name, owner FROM pets ORDER BY name")
+    ///     .fetch_all(&mut conn)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     pets,
+    ///     [
+    ///         Pet { name: "cat".into(), owner: Some("larry".into()) },
+    ///         Pet { name: "dog".into(), owner: None },
+    ///     ]
+    /// );
+    /// # });
+    /// ```
+    impl Type<Sqlite> for ZCString {
+        fn type_info() -> SqliteTypeInfo {
+            <&str as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <&str as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for ZCString {
+        fn encode_by_ref(
+            &self,
+            args: &mut Vec<SqliteArgumentValue<'q>>,
+        ) -> Result<IsNull, BoxDynError> {
+            args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+            Ok(IsNull::No)
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for ZCString {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(ZCString::from_str_with_source(
+                <&str as Decode<Sqlite>>::decode(value)?,
+            ))
+        }
+    }
+}
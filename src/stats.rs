@@ -0,0 +1,219 @@
+// Copyright (c) 2026 CyberNestSticks LLC
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Author: Lawrence (Larry) Foard
+
+//! Opt-in counters of zero-copy hits and allocating misses.
+//!
+//! While a [`StatsGuard`] is alive on a thread, [`ZCString::from_substr`],
+//! [`ZCString::from_str_with_source`] and deserialization count each string
+//! created as a hit when it borrows from its source, or as a miss when it is
+//! copied. Outside of a scope counting costs one thread-local read.
+//!
+//! Scopes may be nested, each reports the strings created during its own
+//! lifetime, inner scopes included.
+//!
+//! **Requires the `std` feature.**
+//!
+//! ### Example
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! # use arcstr::literal;
+//! # use serde::Deserialize;
+//! # use zcstring::{serde_json_from_zcstring, ZCString};
+//! #[derive(Deserialize)]
+//! struct LogEntry {
+//!     level: ZCString,
+//!     message: ZCString,
+//! }
+//!
+//! let input = [
+//!     literal!(r#"{"level": "error", "message": "Connection lost"}"#),
+//!     literal!(r#"{"level": "warning", "message": "Cat on keyboard"}"#),
+//!     literal!(r#"{"level": "info", "message": "Crow pecked camera"}"#),
+//!     literal!(r#"{"level": "error", "message": "Raven pecked camera, now offline"}"#),
+//!     literal!(r#"{"level": "error", "message": "Escaped \" "}"#),
+//! ];
+//!
+//! let (entries, stats) = ZCString::with_stats(|| {
+//!     input
+//!         .into_iter()
+//!         .map(|line| serde_json_from_zcstring::<LogEntry>(line.into()).unwrap())
+//!         .collect::<Vec<_>>()
+//! });
+//!
+//! // only the escaped message had to be copied
+//! assert_eq!(entries.len(), 5);
+//! assert_eq!(stats.hits, 9);
+//! assert_eq!(stats.misses, 1);
+//! assert_eq!(stats.bytes_copied, "Escaped \" ".len() as u64);
+//! # }
+//! ```
+
+use crate::ZCString;
+use std::cell::Cell;
+
+thread_local! {
+    /// Number of live StatsGuards on this thread, nothing is counted at 0.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// Running totals while any StatsGuard is alive.
+    static TOTALS: Cell<ZCStats> = const { Cell::new(ZCStats::new()) };
+}
+
+/// Counts of strings created zero-copy and by allocating, see the
+/// [module documentation](self).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ZCStats {
+    /// Strings created as a view of their source.
+    pub hits: u64,
+    /// Strings that had to be copied into a new buffer.
+    pub misses: u64,
+    /// Total length of the copied strings.
+    pub bytes_copied: u64,
+}
+
+impl ZCStats {
+    const fn new() -> Self {
+        ZCStats {
+            hits: 0,
+            misses: 0,
+            bytes_copied: 0,
+        }
+    }
+
+    /// Returns the fraction of strings created zero-copy, or `None` if no
+    /// strings were created.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// An RAII scope counting zero-copy hits and misses on the current thread,
+/// see the [module documentation](self).
+///
+/// Like [`crate::SourceGuard`] it belongs to the thread that created it and
+/// is not `Send`.
+///
+/// ### Example
+/// ```
+/// # use zcstring::stats::StatsGuard;
+/// # use zcstring::ZCString;
+/// let source = ZCString::from("cats and dogs");
+/// let guard = StatsGuard::new();
+///
+/// let and = source.from_substr(&source[5..8]);
+/// let copy = source.from_substr("frogs");
+///
+/// let stats = guard.stats();
+/// assert_eq!((stats.hits, stats.misses, stats.bytes_copied), (1, 1, 5));
+/// ```
+#[derive(Debug)]
+pub struct StatsGuard {
+    start: ZCStats,
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl StatsGuard {
+    /// Starts counting on the current thread until the guard is dropped.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+
+        StatsGuard {
+            start: TOTALS.with(Cell::get),
+            _not_send: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the counts since this guard was created.
+    pub fn stats(&self) -> ZCStats {
+        let now = TOTALS.with(Cell::get);
+
+        ZCStats {
+            hits: now.hits - self.start.hits,
+            misses: now.misses - self.start.misses,
+            bytes_copied: now.bytes_copied - self.start.bytes_copied,
+        }
+    }
+}
+
+impl Drop for StatsGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| {
+            depth.set(depth.get() - 1);
+
+            // start over once the outermost scope ends
+            if depth.get() == 0 {
+                TOTALS.with(|totals| totals.set(ZCStats::new()));
+            }
+        });
+    }
+}
+
+impl ZCString {
+    /// Runs `f` counting the strings it creates zero-copy and by allocating
+    /// on the current thread, returning its result with the counts. See the
+    /// [`stats`](crate::stats) module.
+    ///
+    /// **Requires the `std` feature.**
+    ///
+    /// ### Example
+    /// ```
+    /// # use zcstring::ZCString;
+    /// let source = ZCString::from("cats and dogs");
+    ///
+    /// let (words, stats) = ZCString::with_stats(|| {
+    ///     ZCString::with_source(source.clone(), |source| {
+    ///         let mut words: Vec<ZCString> = source.split(' ').map(ZCString::from).collect();
+    ///         words.push(ZCString::from("frogs"));
+    ///         words
+    ///     })
+    /// });
+    ///
+    /// assert_eq!(words, ["cats", "and", "dogs", "frogs"]);
+    /// assert_eq!(stats.hits, 3);
+    /// assert_eq!(stats.misses, 1);
+    /// assert_eq!(stats.hit_rate(), Some(0.75));
+    /// ```
+    pub fn with_stats<F, R>(f: F) -> (R, ZCStats)
+    where
+        F: FnOnce() -> R,
+    {
+        let guard = StatsGuard::new();
+        let result = f();
+        (result, guard.stats())
+    }
+}
+
+#[inline]
+fn record(update: impl FnOnce(&mut ZCStats)) {
+    if DEPTH.with(Cell::get) > 0 {
+        TOTALS.with(|totals| {
+            let mut stats = totals.get();
+            update(&mut stats);
+            totals.set(stats);
+        });
+    }
+}
+
+// a string was created as a view of its source
+#[inline]
+pub(crate) fn record_hit() {
+    record(|stats| stats.hits += 1);
+}
+
+// a string of len bytes was copied into a new buffer
+#[inline]
+pub(crate) fn record_miss(len: usize) {
+    record(|stats| {
+        stats.misses += 1;
+        stats.bytes_copied += len as u64;
+    });
+}